@@ -1,3 +1,8 @@
 pub mod board;
+pub mod client;
+pub mod error;
+pub mod mutation;
+pub mod request;
 pub mod task;
+pub mod trash;
 pub mod user;
@@ -1,79 +1,262 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{app::App, helper::input::InputContentVariants, API_URL};
+use crate::{
+    api::{
+        error::ApiError,
+        mutation::Mutation,
+        request::{BoardChange, PatchBoard},
+    },
+    app::App,
+    helper::input::InputContentVariants,
+};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// What a board is for, stored by the api as the `special` integer.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(from = "Option<i32>", into = "Option<i32>")]
+pub enum BoardKind {
+    /// A board created by the user.
+    #[default]
+    Normal,
+    /// `special == 1`
+    Inbox,
+    /// `special == 2`, deleted tasks end up here.
+    Trash,
+    /// `special == 3`, shown together with the inbox.
+    Archive,
+}
+
+impl BoardKind {
+    /// Name used when the board has to be created.
+    pub fn default_name(&self) -> &'static str {
+        match self {
+            BoardKind::Normal => "Board",
+            BoardKind::Inbox => "Inbox",
+            BoardKind::Trash => "Trash",
+            BoardKind::Archive => "Archive",
+        }
+    }
+}
+
+impl From<Option<i32>> for BoardKind {
+    fn from(special: Option<i32>) -> Self {
+        match special {
+            Some(1) => BoardKind::Inbox,
+            Some(2) => BoardKind::Trash,
+            Some(3) => BoardKind::Archive,
+            _ => BoardKind::Normal,
+        }
+    }
+}
+
+impl From<BoardKind> for Option<i32> {
+    fn from(kind: BoardKind) -> Self {
+        match kind {
+            BoardKind::Normal => None,
+            BoardKind::Inbox => Some(1),
+            BoardKind::Trash => Some(2),
+            BoardKind::Archive => Some(3),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Board {
     pub uuid: String,
     pub name: String,
     pub user_uuid: String,
-    pub special: Option<i32>,
+    #[serde(rename = "special", default)]
+    pub kind: BoardKind,
+    /// Position in the board column, boards are listed in ascending order.
+    #[serde(default)]
+    pub order: i32,
+}
+
+/// Lookups on a list of boards.
+pub trait Boards {
+    /// The first board of the kind, special boards exist at most once.
+    fn of_kind(&self, kind: BoardKind) -> Option<&Board>;
+    fn by_uuid(&self, uuid: &str) -> Option<&Board>;
+}
+
+impl Boards for [Board] {
+    fn of_kind(&self, kind: BoardKind) -> Option<&Board> {
+        self.iter().find(|board| board.kind == kind)
+    }
+
+    fn by_uuid(&self, uuid: &str) -> Option<&Board> {
+        self.iter().find(|board| board.uuid == uuid)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum BoardAction {
+    RenameBoard(String),
+    ChangeOrder(i32),
+}
+
+impl BoardAction {
+    /// Body of the `/patch/board` request.
+    pub fn request<'a>(&'a self, board_uuid: &'a str) -> PatchBoard<'a> {
+        let change = match self {
+            BoardAction::RenameBoard(name) => BoardChange::RenameBoard { name },
+            BoardAction::ChangeOrder(order) => BoardChange::ChangeOrder { order: *order },
+        };
+        PatchBoard { board_uuid, change }
+    }
+
+    /// Applies the action to the local copy of the board.
+    pub fn apply(&self, board: &mut Board) {
+        match self {
+            BoardAction::RenameBoard(name) => board.name = name.clone(),
+            BoardAction::ChangeOrder(order) => board.order = *order,
+        }
+    }
+
+    /// Restores the field the action changed from `before`.
+    pub fn revert(&self, board: &mut Board, before: &Board) {
+        match self {
+            BoardAction::RenameBoard(_) => board.name = before.name.clone(),
+            BoardAction::ChangeOrder(_) => board.order = before.order,
+        }
+    }
+
+    /// The action that takes the board back to `before`.
+    pub fn inverse(&self, before: &Board) -> BoardAction {
+        match self {
+            BoardAction::RenameBoard(_) => BoardAction::RenameBoard(before.name.clone()),
+            BoardAction::ChangeOrder(_) => BoardAction::ChangeOrder(before.order),
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            BoardAction::RenameBoard(_) => "rename board",
+            BoardAction::ChangeOrder(_) => "reorder board",
+        }
+    }
 }
 
-pub async fn get_all_user_boards(app: &mut App) -> Option<Vec<Board>> {
-    let client = &app.reqwest_client;
-    let url = format!("{}/get/all_user_board?device_identifier=tty", API_URL);
-    //TODO: Error handling
-    let response = client.get(&url).send().await.unwrap().text().await.unwrap();
-    let boards: Vec<Board> = serde_json::from_str(&response).unwrap();
-    Some(boards)
+pub async fn get_all_user_boards(app: &App) -> Result<Vec<Board>, ApiError> {
+    app.client.all_boards().await
 }
 
 pub async fn create_board(app: &mut App) {
-    let url = format!("{}/post/board?device_identifier=tty", API_URL);
+    let Some(user) = app.user.as_ref() else {
+        return;
+    };
     let board: Board = match &app.input_content.variant {
         InputContentVariants::CreateBoard { name } => Board {
             uuid: uuid::Uuid::new_v4().to_string(),
             name: name.to_string(),
-            user_uuid: app.user.as_ref().unwrap().uuid.clone(),
-            special: None,
+            user_uuid: user.uuid.clone(),
+            kind: BoardKind::Normal,
+            order: app
+                .boards
+                .items
+                .iter()
+                .map(|board| board.order + 1)
+                .max()
+                .unwrap_or(0),
         },
         _ => {
             return;
         }
     };
 
-    app.boards.items.push(board.clone());
-
-    let board_json = serde_json::to_string(&board).unwrap();
-
-    let client = app.reqwest_client.clone();
-    // Spawn the async task
-    tokio::spawn(async move {
-        let _response = client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .body(board_json)
-            .send()
-            .await
-            .unwrap()
-            .text()
-            .await
-            .unwrap();
-    });
+    app.apply(Mutation::CreateBoard(board));
 }
 
-pub async fn delete_board(app: &mut App) {
-    let board_uuid = app.boards.selected().unwrap().uuid.clone();
+/// Returns the uuid of the user's board of the given kind.
+///
+/// Accounts missing one of the special boards get it created on first use.
+pub fn ensure_board(app: &mut App, kind: BoardKind) -> Option<String> {
+    if let Some(board) = app.boards.items.of_kind(kind) {
+        return Some(board.uuid.clone());
+    }
+
+    let board = Board {
+        uuid: uuid::Uuid::new_v4().to_string(),
+        name: kind.default_name().to_string(),
+        user_uuid: app.user.as_ref()?.uuid.clone(),
+        kind,
+        order: app
+            .boards
+            .items
+            .iter()
+            .map(|board| board.order + 1)
+            .max()
+            .unwrap_or(0),
+    };
+    let uuid = board.uuid.clone();
+    app.apply(Mutation::CreateBoard(board));
+    Some(uuid)
+}
 
-    app.boards.items.retain(|board| board.uuid != board_uuid);
+/// Deletes the board, if it still exists.
+pub async fn delete_board(app: &mut App, board_uuid: &str) {
+    let Some(index) = app
+        .boards
+        .items
+        .iter()
+        .position(|board| board.uuid == board_uuid)
+    else {
+        return;
+    };
+    let board = app.boards.items[index].clone();
+    let tasks = app
+        .task_data
+        .iter()
+        .filter(|task| task.board_uuid == board_uuid)
+        .cloned()
+        .collect();
 
-    let client = app.reqwest_client.clone();
+    app.apply(Mutation::DeleteBoard {
+        board,
+        index,
+        tasks,
+    });
+}
 
-    // Spawn the async task
-    tokio::spawn(async move {
-        let url = format!(
-            "{}/delete/board/{}?device_identifier=tty",
-            API_URL, board_uuid
-        );
+/// Renames the selected board to the name in the [`InputContentVariants::RenameBoard`] form.
+pub async fn rename_board(app: &mut App) {
+    let name = match &app.input_content.variant {
+        InputContentVariants::RenameBoard { name } => name.to_string(),
+        _ => return,
+    };
+    let before = match app.boards.selected() {
+        Some(board) if board.name != name => board.clone(),
+        _ => return,
+    };
 
-        let _response = client
-            .delete(&url)
-            .send()
-            .await
-            .unwrap()
-            .text()
-            .await
-            .unwrap();
+    app.apply(Mutation::PatchBoard {
+        before,
+        action: BoardAction::RenameBoard(name),
     });
 }
+
+/// Moves the selected board up (`-1`) or down (`1`) in the board column.
+///
+/// The boards are renumbered, only the ones whose order changed are patched.
+pub async fn reorder_board(app: &mut App, offset: isize) {
+    let Some(index) = app.boards.state.selected() else {
+        return;
+    };
+    let target = index as isize + offset;
+    if index >= app.boards.items.len() || target < 0 || target as usize >= app.boards.items.len() {
+        return;
+    }
+    let target = target as usize;
+
+    app.boards.items.swap(index, target);
+    app.boards.state.select(Some(target));
+
+    let boards = app.boards.items.clone();
+    for (order, before) in boards.into_iter().enumerate() {
+        if before.order != order as i32 {
+            app.apply(Mutation::PatchBoard {
+                before,
+                action: BoardAction::ChangeOrder(order as i32),
+            });
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use std::sync::Arc;
+
+use reqwest::cookie::{CookieStore, Jar};
+use reqwest::{RequestBuilder, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::api::board::Board;
+use crate::api::error::ApiError;
+use crate::api::request::{CreateUser, Login, PatchBoard, PatchTask};
+use crate::api::task::Task;
+use crate::api::user::{Credentials, User};
+
+/// Typed client for the quicktick api.
+///
+/// Cloning is cheap, the underlying [`reqwest::Client`] (and its cookie store) is shared.
+#[derive(Debug, Clone)]
+pub struct QuickTickClient {
+    http: reqwest::Client,
+    cookies: Arc<Jar>,
+    base_url: String,
+}
+
+impl QuickTickClient {
+    /// Constructs a new client for the api at `base_url`.
+    pub fn new(base_url: String) -> Self {
+        let cookies = Arc::new(Jar::default());
+        Self {
+            http: reqwest::ClientBuilder::new()
+                .user_agent("quicktick-tty")
+                .cookie_provider(cookies.clone())
+                .timeout(std::time::Duration::from_secs(5))
+                .build()
+                .unwrap(),
+            cookies,
+            base_url,
+        }
+    }
+
+    /// The cookies the server set, as a `Cookie` header value.
+    pub fn session_cookies(&self) -> Option<String> {
+        let url = Url::parse(&self.base_url).ok()?;
+        let cookies = self.cookies.cookies(&url)?;
+        cookies.to_str().ok().map(str::to_string)
+    }
+
+    /// Puts cookies saved by [`QuickTickClient::session_cookies`] back into the cookie store.
+    pub fn restore_session(&self, cookies: &str) {
+        let Ok(url) = Url::parse(&self.base_url) else {
+            return;
+        };
+        for cookie in cookies.split("; ").filter(|cookie| !cookie.is_empty()) {
+            self.cookies.add_cookie_str(cookie, &url);
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}?device_identifier=tty", self.base_url, path)
+    }
+
+    /// Sends the request and returns the body of a successful response.
+    async fn send(&self, request: RequestBuilder) -> Result<String, ApiError> {
+        match self.response(request).await? {
+            (status, body) if status.is_success() => Ok(body),
+            (StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN, _) => Err(ApiError::AuthExpired),
+            (status, body) => Err(ApiError::Status {
+                status,
+                body: server_message(body),
+            }),
+        }
+    }
+
+    /// Sends the request and returns the status and body of whatever came back.
+    async fn response(&self, request: RequestBuilder) -> Result<(StatusCode, String), ApiError> {
+        let response = request.send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        Ok((status, body))
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T, ApiError> {
+        let body = self.send(self.http.get(self.url(path))).await?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    async fn send_json<T: Serialize>(
+        &self,
+        request: RequestBuilder,
+        body: &T,
+    ) -> Result<(), ApiError> {
+        let body = serde_json::to_string(body)?;
+        self.send(
+            request
+                .header("Content-Type", "application/json")
+                .body(body),
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn all_tasks(&self) -> Result<Vec<Task>, ApiError> {
+        self.get_json("/get/all_user_tasks").await
+    }
+
+    pub async fn all_boards(&self) -> Result<Vec<Board>, ApiError> {
+        self.get_json("/get/all_user_board").await
+    }
+
+    pub async fn create_task(&self, task: &Task) -> Result<(), ApiError> {
+        self.send_json(self.http.post(self.url("/post/create_task")), task)
+            .await
+    }
+
+    /// Sends a `/patch/task` request, see [`crate::api::task::Action::request`].
+    pub async fn patch_task(&self, body: &PatchTask<'_>) -> Result<(), ApiError> {
+        self.send_json(self.http.patch(self.url("/patch/task")), body)
+            .await
+    }
+
+    pub async fn delete_task(&self, task_uuid: &str) -> Result<(), ApiError> {
+        let url = self.url(&format!("/delete/task/{}", task_uuid));
+        self.send(self.http.delete(url)).await?;
+        Ok(())
+    }
+
+    pub async fn create_board(&self, board: &Board) -> Result<(), ApiError> {
+        self.send_json(self.http.post(self.url("/post/board")), board)
+            .await
+    }
+
+    /// Sends a `/patch/board` request, see [`crate::api::board::BoardAction::request`].
+    pub async fn patch_board(&self, body: &PatchBoard<'_>) -> Result<(), ApiError> {
+        self.send_json(self.http.patch(self.url("/patch/board")), body)
+            .await
+    }
+
+    pub async fn delete_board(&self, board_uuid: &str) -> Result<(), ApiError> {
+        let url = self.url(&format!("/delete/board/{}", board_uuid));
+        self.send(self.http.delete(url)).await?;
+        Ok(())
+    }
+
+    /// Logs in, the session cookie ends up in the client's cookie store.
+    ///
+    /// The credentials are posted as json, servers that don't know that route
+    /// yet get them percent-encoded in the `/login/{email}/{password}` path.
+    /// Only a `405` or a bare `404` means the route is missing, a `404` with a
+    /// message is the new route answering and must not leak the password.
+    pub async fn login(&self, credentials: &Credentials) -> Result<(), ApiError> {
+        let body = serde_json::to_string(&Login {
+            email: &credentials.email,
+            password: &credentials.password,
+        })?;
+        let request = self
+            .http
+            .post(self.url("/login"))
+            .header("Content-Type", "application/json")
+            .body(body);
+
+        let (status, body) = match self.response(request).await? {
+            (StatusCode::METHOD_NOT_ALLOWED, _) => self.legacy_login(credentials).await?,
+            (StatusCode::NOT_FOUND, body) if body.trim().is_empty() => {
+                self.legacy_login(credentials).await?
+            }
+            response => response,
+        };
+
+        match status {
+            status if status.is_success() => Ok(()),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN if body.trim().is_empty() => {
+                Err(ApiError::InvalidCredentials)
+            }
+            status => Err(ApiError::Status {
+                status,
+                body: server_message(body),
+            }),
+        }
+    }
+
+    async fn legacy_login(
+        &self,
+        credentials: &Credentials,
+    ) -> Result<(StatusCode, String), ApiError> {
+        let url = login_path_url(&self.base_url, credentials)?;
+        self.response(self.http.get(url)).await
+    }
+
+    /// Fetches the user the session cookie belongs to.
+    pub async fn current_user(&self) -> Result<User, ApiError> {
+        let body = self
+            .send(self.http.get(self.url("/login/cookie/cookie")))
+            .await?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    pub async fn create_user(&self, body: &CreateUser<'_>) -> Result<(), ApiError> {
+        self.send_json(self.http.post(self.url("/post/create_user")), body)
+            .await
+    }
+
+    pub async fn logout(&self) -> Result<(), ApiError> {
+        self.send(self.http.get(self.url("/logout"))).await?;
+        Ok(())
+    }
+}
+
+/// `/login/{email}/{password}` url of older servers, with the credentials
+/// percent-encoded into one path segment each.
+pub fn login_path_url(base_url: &str, credentials: &Credentials) -> Result<Url, ApiError> {
+    let invalid_url = || ApiError::InvalidUrl(base_url.to_string());
+    let mut url = Url::parse(base_url).map_err(|_| invalid_url())?;
+    url.path_segments_mut()
+        .map_err(|_| invalid_url())?
+        .pop_if_empty()
+        .extend(["login", &credentials.email, &credentials.password]);
+    url.query_pairs_mut()
+        .append_pair("device_identifier", "tty");
+    Ok(url)
+}
+
+/// The server sends its errors as json strings, unquotes them.
+fn server_message(body: String) -> String {
+    serde_json::from_str::<String>(&body).unwrap_or(body)
+}
@@ -0,0 +1,77 @@
+use std::fmt;
+
+use reqwest::StatusCode;
+
+/// Everything that can go wrong while talking to the quicktick api.
+#[derive(Debug)]
+pub enum ApiError {
+    /// The request didn't get a response (no connection, dns, timeout, ...).
+    Transport(reqwest::Error),
+    /// The server answered with a non success status code.
+    Status { status: StatusCode, body: String },
+    /// The session cookie is missing or no longer accepted.
+    AuthExpired,
+    /// The server rejected the email and password.
+    InvalidCredentials,
+    /// The response body didn't match the expected json.
+    Decode(serde_json::Error),
+    /// The configured server url can't be used.
+    InvalidUrl(String),
+    /// The cache holds offline changes of another account, signing in would
+    /// overwrite them.
+    UnsyncedChanges { email: String, count: usize },
+}
+
+impl ApiError {
+    /// Whether the request failed because the server couldn't be reached.
+    pub fn is_transport(&self) -> bool {
+        matches!(self, ApiError::Transport(_))
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Transport(err) if err.is_timeout() => write!(f, "request timed out"),
+            ApiError::Transport(err) if err.is_connect() => write!(f, "can't reach the server"),
+            ApiError::Transport(err) => write!(f, "request failed: {}", err),
+            ApiError::Status { status, body } if body.is_empty() => {
+                write!(f, "server responded with {}", status)
+            }
+            ApiError::Status { status, body } => {
+                write!(f, "server responded with {}: {}", status, body)
+            }
+            ApiError::AuthExpired => write!(f, "session expired, log in again"),
+            ApiError::InvalidCredentials => write!(f, "wrong email or password"),
+            ApiError::Decode(err) => write!(f, "unexpected response from the server: {}", err),
+            ApiError::InvalidUrl(url) => write!(f, "invalid server url {}", url),
+            ApiError::UnsyncedChanges { email, count } => write!(
+                f,
+                "{} changes of {} aren't synced yet, log in as them first",
+                count, email
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ApiError::Transport(err) => Some(err),
+            ApiError::Decode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ApiError {
+    fn from(err: reqwest::Error) -> Self {
+        ApiError::Transport(err)
+    }
+}
+
+impl From<serde_json::Error> for ApiError {
+    fn from(err: serde_json::Error) -> Self {
+        ApiError::Decode(err)
+    }
+}
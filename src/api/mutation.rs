@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api::board::{Board, BoardAction};
+use crate::api::client::QuickTickClient;
+use crate::api::error::ApiError;
+use crate::api::task::{Action, Task};
+use crate::app::App;
+
+/// A change to the user's data.
+///
+/// Mutations are applied to [`App`] right away and sent to the api in the
+/// background, if the request fails the change gets rolled back.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum Mutation {
+    CreateTask(Task),
+    /// Permanently deletes the task, holds the task as it was before.
+    DeleteTask(Task),
+    /// Patches the task, `before` is the task before the action was applied.
+    PatchTask {
+        before: Task,
+        action: Action,
+    },
+    CreateBoard(Board),
+    /// Patches the board, `before` is the board before the action was applied.
+    PatchBoard {
+        before: Board,
+        action: BoardAction,
+    },
+    /// Deletes the board with its tasks, `index` is its position in the
+    /// board list and `tasks` the tasks it had.
+    DeleteBoard {
+        board: Board,
+        index: usize,
+        #[serde(default)]
+        tasks: Vec<Task>,
+    },
+}
+
+impl Mutation {
+    /// Applies the change to the local data.
+    pub fn apply(&self, app: &mut App) {
+        self.apply_to_tasks(&mut app.task_data);
+        self.apply_to_boards(&mut app.boards.items);
+    }
+
+    pub fn apply_to_tasks(&self, tasks: &mut Vec<Task>) {
+        match self {
+            Mutation::CreateTask(task) => {
+                if !tasks.iter().any(|t| t.uuid == task.uuid) {
+                    tasks.push(task.clone());
+                }
+            }
+            Mutation::DeleteTask(task) => tasks.retain(|t| t.uuid != task.uuid),
+            Mutation::PatchTask { before, action } => {
+                if let Some(task) = tasks.iter_mut().find(|t| t.uuid == before.uuid) {
+                    action.apply(task);
+                }
+            }
+            Mutation::DeleteBoard { board, .. } => tasks.retain(|t| t.board_uuid != board.uuid),
+            Mutation::CreateBoard(_) | Mutation::PatchBoard { .. } => {}
+        }
+    }
+
+    pub fn apply_to_boards(&self, boards: &mut Vec<Board>) {
+        match self {
+            Mutation::CreateBoard(board) if !boards.iter().any(|b| b.uuid == board.uuid) => {
+                boards.push(board.clone())
+            }
+            Mutation::PatchBoard { before, action } => {
+                if let Some(board) = boards.iter_mut().find(|b| b.uuid == before.uuid) {
+                    action.apply(board);
+                }
+            }
+            Mutation::DeleteBoard { board, .. } => boards.retain(|b| b.uuid != board.uuid),
+            _ => {}
+        }
+    }
+
+    /// Reverts [`Mutation::apply`] after the api rejected the change.
+    pub fn rollback(&self, app: &mut App) {
+        match self {
+            Mutation::CreateTask(task) => app.task_data.retain(|t| t.uuid != task.uuid),
+            Mutation::DeleteTask(task) => {
+                if !app.task_data.iter().any(|t| t.uuid == task.uuid) {
+                    app.task_data.push(task.clone());
+                }
+            }
+            Mutation::PatchTask { before, action } => {
+                if let Some(task) = app.task_data.iter_mut().find(|t| t.uuid == before.uuid) {
+                    action.revert(task, before);
+                }
+            }
+            Mutation::CreateBoard(board) => app.boards.items.retain(|b| b.uuid != board.uuid),
+            Mutation::PatchBoard { before, action } => {
+                if let Some(board) = app.boards.items.iter_mut().find(|b| b.uuid == before.uuid) {
+                    action.revert(board, before);
+                }
+            }
+            Mutation::DeleteBoard {
+                board,
+                index,
+                tasks,
+            } => {
+                if !app.boards.items.iter().any(|b| b.uuid == board.uuid) {
+                    let index = (*index).min(app.boards.items.len());
+                    app.boards.items.insert(index, board.clone());
+                }
+                for task in tasks {
+                    if !app.task_data.iter().any(|t| t.uuid == task.uuid) {
+                        app.task_data.push(task.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    /// The mutations that undo this one, in the order they have to be sent.
+    ///
+    /// `boards` and `tasks` are the current data. Deleted tasks and boards are
+    /// recreated with their old uuid, a board before its tasks.
+    pub fn inverse(&self, boards: &[Board], tasks: &[Task]) -> Vec<Mutation> {
+        match self {
+            Mutation::CreateTask(task) => vec![Mutation::DeleteTask(task.clone())],
+            Mutation::DeleteTask(task) => vec![Mutation::CreateTask(task.clone())],
+            Mutation::PatchTask { before, action } => {
+                let mut after = before.clone();
+                action.apply(&mut after);
+                vec![Mutation::PatchTask {
+                    action: action.inverse(before),
+                    before: after,
+                }]
+            }
+            Mutation::CreateBoard(board) => vec![Mutation::DeleteBoard {
+                board: board.clone(),
+                index: boards
+                    .iter()
+                    .position(|b| b.uuid == board.uuid)
+                    .unwrap_or(boards.len()),
+                tasks: tasks
+                    .iter()
+                    .filter(|t| t.board_uuid == board.uuid)
+                    .cloned()
+                    .collect(),
+            }],
+            Mutation::PatchBoard { before, action } => {
+                let mut after = before.clone();
+                action.apply(&mut after);
+                vec![Mutation::PatchBoard {
+                    action: action.inverse(before),
+                    before: after,
+                }]
+            }
+            Mutation::DeleteBoard { board, tasks, .. } => {
+                std::iter::once(Mutation::CreateBoard(board.clone()))
+                    .chain(tasks.iter().cloned().map(Mutation::CreateTask))
+                    .collect()
+            }
+        }
+    }
+
+    /// Sends the change to the api.
+    pub async fn send(&self, client: &QuickTickClient) -> Result<(), ApiError> {
+        match self {
+            Mutation::CreateTask(task) => client.create_task(task).await,
+            Mutation::DeleteTask(task) => client.delete_task(&task.uuid).await,
+            Mutation::PatchTask { before, action } => {
+                client.patch_task(&action.request(&before.uuid)).await
+            }
+            Mutation::CreateBoard(board) => client.create_board(board).await,
+            Mutation::PatchBoard { before, action } => {
+                client.patch_board(&action.request(&before.uuid)).await
+            }
+            Mutation::DeleteBoard { board, .. } => client.delete_board(&board.uuid).await,
+        }
+    }
+
+    /// Short description for notifications, e.g. `delete task "Buy milk"`.
+    pub fn describe(&self) -> String {
+        match self {
+            Mutation::CreateTask(task) => format!("create task \"{}\"", task.name),
+            Mutation::DeleteTask(task) => format!("delete task \"{}\"", task.name),
+            Mutation::PatchTask { before, action } => {
+                format!("{} \"{}\"", action.describe(), before.name)
+            }
+            Mutation::CreateBoard(board) => format!("create board \"{}\"", board.name),
+            Mutation::PatchBoard { before, action } => {
+                format!("{} \"{}\"", action.describe(), before.name)
+            }
+            Mutation::DeleteBoard { board, .. } => format!("delete board \"{}\"", board.name),
+        }
+    }
+}
+
+/// Result of replaying the offline queue, see [`replay`].
+#[derive(Debug)]
+pub struct ReplayOutcome {
+    /// The mutations that got an answer from the api, in queue order.
+    pub results: Vec<(Mutation, Result<(), ApiError>)>,
+    /// Whether the replay stopped because the api became unreachable.
+    pub unreachable: bool,
+}
+
+/// Sends the queued mutations in order.
+///
+/// Stops at the first transport error, the rest of the queue stays queued.
+pub async fn replay(client: QuickTickClient, queue: Vec<Mutation>) -> ReplayOutcome {
+    let mut results = Vec::new();
+
+    for mutation in queue {
+        let result = mutation.send(&client).await;
+        if result.as_ref().is_err_and(ApiError::is_transport) {
+            return ReplayOutcome {
+                results,
+                unreachable: true,
+            };
+        }
+        results.push((mutation, result));
+    }
+
+    ReplayOutcome {
+        results,
+        unreachable: false,
+    }
+}
@@ -0,0 +1,51 @@
+use serde::Serialize;
+
+/// Body of `/login`.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct Login<'a> {
+    pub email: &'a str,
+    pub password: &'a str,
+}
+
+/// Body of `/post/create_user`.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateUser<'a> {
+    pub email: &'a str,
+    pub password: &'a str,
+    pub name: &'a str,
+}
+
+/// Body of `/patch/task`.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct PatchTask<'a> {
+    pub task_uuid: &'a str,
+    #[serde(flatten)]
+    pub change: TaskChange<'a>,
+}
+
+/// The `action` of a `/patch/task` request and its argument.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "action")]
+pub enum TaskChange<'a> {
+    RenameTask { name: &'a str },
+    ChangeDesc { description: &'a str },
+    ToggleTask,
+    ChangeOrder { order: i32 },
+    MoveBoard { board_uuid: &'a str },
+}
+
+/// Body of `/patch/board`.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct PatchBoard<'a> {
+    pub board_uuid: &'a str,
+    #[serde(flatten)]
+    pub change: BoardChange<'a>,
+}
+
+/// The `action` of a `/patch/board` request and its argument.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "action")]
+pub enum BoardChange<'a> {
+    RenameBoard { name: &'a str },
+    ChangeOrder { order: i32 },
+}
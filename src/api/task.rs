@@ -1,14 +1,18 @@
-use core::panic;
-
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    api::{
+        board::{BoardKind, Boards},
+        error::ApiError,
+        mutation::Mutation,
+        request::{PatchTask, TaskChange},
+        trash::trash_task,
+    },
     app::App,
-    helper::input::{InputContent, InputContentVariants},
-    API_URL,
+    helper::input::InputContentVariants,
 };
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Task {
     pub uuid: String,
     pub name: String,
@@ -16,56 +20,29 @@ pub struct Task {
     pub completed: bool,
     pub user_uuid: String,
     pub board_uuid: String,
+    /// Position within the board, tasks are listed in ascending order.
+    #[serde(default)]
+    pub order: i32,
 }
-pub async fn get_all_tasks(app: &mut App) -> Option<Vec<Task>> {
-    let client = &app.reqwest_client;
-    let url = format!("{}/get/all_user_tasks?device_identifier=tty", API_URL);
-    let response = client.get(&url).send().await.unwrap().text().await.unwrap();
-    let tasks: Vec<Task> = serde_json::from_str(&response).unwrap();
-    Some(tasks)
+
+pub async fn get_all_tasks(app: &App) -> Result<Vec<Task>, ApiError> {
+    app.client.all_tasks().await
 }
 
+/// Moves the selected task to the trash, or deletes it for good when it's already there.
 pub async fn delete_task(app: &mut App) {
-    let task_uuid = app.tasks.selected().unwrap().clone().uuid;
-    let board_special = app.boards.selected().unwrap().special;
-    if board_special.is_some_and(|special| special == 1) || board_special.is_none() {
-        app.task_data.iter_mut().for_each(|task| {
-            if task.uuid == task_uuid {
-                task.board_uuid = board_special.unwrap().to_string();
-            }
-        });
-
-        let special_2_board_uuid = app
-            .boards
-            .items
-            .iter()
-            .find(|board| board.special == Some(2))
-            .unwrap()
-            .uuid
-            .clone();
-        patch_task(app, Action::MoveBoard(special_2_board_uuid)).await;
-    } else if board_special.unwrap() == 2 {
-        app.task_data.retain(|task| task.uuid != task_uuid);
-
-        let client = app.reqwest_client.clone();
-
-        // Spawn the async task
-        tokio::spawn(async move {
-            let url = format!(
-                "{}/delete/task/{}?device_identifier=tty",
-                API_URL, task_uuid
-            );
-
-            //TODO: Error handling
-            let response = client
-                .delete(&url)
-                .send()
-                .await
-                .unwrap()
-                .text()
-                .await
-                .unwrap();
-        });
+    let task = match app.tasks.selected() {
+        Some(task) => task.clone(),
+        None => return,
+    };
+    let board_kind = match app.boards.selected() {
+        Some(board) => board.kind,
+        None => return,
+    };
+
+    match board_kind {
+        BoardKind::Trash => app.apply(Mutation::DeleteTask(task)),
+        BoardKind::Normal | BoardKind::Inbox | BoardKind::Archive => trash_task(app).await,
     }
 }
 
@@ -76,81 +53,71 @@ pub async fn create_task(app: &mut App) {
             name: name.to_string(),
             description: description.to_string(),
             completed: false,
-            user_uuid: app.user.as_ref().unwrap().uuid.clone(),
-            board_uuid: app.boards.selected().unwrap().uuid.clone(),
+            user_uuid: match &app.user {
+                Some(user) => user.uuid.clone(),
+                None => return,
+            },
+            board_uuid: match app.boards.selected() {
+                Some(board) => board.uuid.clone(),
+                None => return,
+            },
+            order: app
+                .tasks
+                .items
+                .iter()
+                .map(|task| task.order + 1)
+                .max()
+                .unwrap_or(0),
         },
         _ => {
             return;
         }
     };
 
-    app.task_data.push(new_task.clone());
-
-    let url = format!("{}/post/create_task?device_identifier=tty", API_URL);
-    let client = app.reqwest_client.clone();
-
-    // Spawn the async task
-    tokio::spawn(async move {
-        let _response = client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .body(serde_json::to_string(&new_task).unwrap())
-            .send()
-            .await
-            .unwrap()
-            .text()
-            .await
-            .unwrap();
-    });
+    app.apply(Mutation::CreateTask(new_task));
 }
 
-pub async fn move_task(_app: &mut App) {
-    todo!()
-}
-
-pub async fn toggle_task(app: &mut App) {
-    // Clone the values you need
+/// Sends the changes made in the [`InputContentVariants::UpdateTask`] form.
+///
+/// Only the fields that differ from the selected task are patched.
+pub async fn update_task(app: &mut App) {
+    let (name, description) = match &app.input_content.variant {
+        InputContentVariants::UpdateTask { name, description } => {
+            (name.to_string(), description.to_string())
+        }
+        _ => return,
+    };
+    let task = match app.tasks.selected() {
+        Some(task) => task.clone(),
+        None => return,
+    };
 
-    let selected_task_index = app.tasks.state.selected().unwrap_or(9999);
-    if selected_task_index == 9999 {
-        return;
+    if name != task.name {
+        patch_task(app, Action::RenameTask(name)).await;
     }
-    if app.tasks.items.len() < selected_task_index || app.tasks.items.is_empty() {
-        return;
+    if description != task.description {
+        patch_task(app, Action::ChangeDesc(description)).await;
     }
+}
 
-    let task_uuid = app.tasks.items[selected_task_index].uuid.clone();
+/// Moves the task to the board, if both still exist.
+pub async fn move_task(app: &mut App, task_uuid: &str, board_uuid: String) {
+    if app.boards.items.by_uuid(&board_uuid).is_none()
+        || app
+            .task_data
+            .iter()
+            .any(|task| task.uuid == task_uuid && task.board_uuid == board_uuid)
+    {
+        return;
+    }
+    patch_task_by_uuid(app, task_uuid, Action::MoveBoard(board_uuid)).await;
+}
 
-    // Toggle the completed field
-    app.task_data.iter_mut().for_each(|task| {
-        if task.uuid == task_uuid {
-            task.completed = !task.completed;
-        }
-    });
-
-    let client = app.reqwest_client.clone();
-
-    // Spawn the async task
-    tokio::spawn(async move {
-        let url = format!("{}/patch/task?device_identifier=tty", API_URL);
-
-        //TODO: Error handling
-        let _response = client
-            .patch(&url)
-            .header("Content-Type", "application/json")
-            .body(format!(
-                "{{\"task_uuid\": \"{}\", \"action\": \"ToggleTask\"}}",
-                task_uuid
-            ))
-            .send()
-            .await
-            .unwrap()
-            .text()
-            .await
-            .unwrap();
-    });
+pub async fn toggle_task(app: &mut App) {
+    patch_task(app, Action::ToggleTask).await;
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum Action {
     RenameTask(String),
     ChangeDesc(String),
@@ -159,46 +126,105 @@ pub enum Action {
     MoveBoard(String),
 }
 
-pub async fn patch_task(app: &mut App, action: Action) {
-    if app.tasks.selected().is_none() {
-        return;
+impl Action {
+    /// Body of the `/patch/task` request.
+    pub fn request<'a>(&'a self, task_uuid: &'a str) -> PatchTask<'a> {
+        let change = match self {
+            Action::RenameTask(name) => TaskChange::RenameTask { name },
+            Action::ChangeDesc(description) => TaskChange::ChangeDesc { description },
+            Action::ToggleTask => TaskChange::ToggleTask,
+            Action::ChangeOrder(order) => TaskChange::ChangeOrder { order: *order },
+            Action::MoveBoard(board_uuid) => TaskChange::MoveBoard { board_uuid },
+        };
+        PatchTask { task_uuid, change }
+    }
+
+    /// Applies the action to the local copy of the task.
+    pub fn apply(&self, task: &mut Task) {
+        match self {
+            Action::RenameTask(name) => task.name = name.clone(),
+            Action::ChangeDesc(description) => task.description = description.clone(),
+            Action::ToggleTask => task.completed = !task.completed,
+            Action::ChangeOrder(order) => task.order = *order,
+            Action::MoveBoard(board_uuid) => task.board_uuid = board_uuid.clone(),
+        }
     }
 
-    let task_uuid = app.tasks.selected().unwrap().uuid.clone();
+    /// Restores the field the action changed from `before`.
+    pub fn revert(&self, task: &mut Task, before: &Task) {
+        match self {
+            Action::RenameTask(_) => task.name = before.name.clone(),
+            Action::ChangeDesc(_) => task.description = before.description.clone(),
+            Action::ToggleTask => task.completed = before.completed,
+            Action::ChangeOrder(_) => task.order = before.order,
+            Action::MoveBoard(_) => task.board_uuid = before.board_uuid.clone(),
+        }
+    }
 
-    let client = app.reqwest_client.clone();
+    /// The action that takes the task back to `before`.
+    pub fn inverse(&self, before: &Task) -> Action {
+        match self {
+            Action::RenameTask(_) => Action::RenameTask(before.name.clone()),
+            Action::ChangeDesc(_) => Action::ChangeDesc(before.description.clone()),
+            Action::ToggleTask => Action::ToggleTask,
+            Action::ChangeOrder(_) => Action::ChangeOrder(before.order),
+            Action::MoveBoard(_) => Action::MoveBoard(before.board_uuid.clone()),
+        }
+    }
 
-    let body = match action {
-        Action::RenameTask(_) => todo!(),
-        Action::ChangeDesc(_) => todo!(),
-        Action::ToggleTask => {
-            format!(
-                "{{\"task_uuid\": \"{}\", \"action\": \"ToggleTask\"}}",
-                task_uuid.clone()
-            )
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Action::RenameTask(_) => "rename task",
+            Action::ChangeDesc(_) => "change description of",
+            Action::ToggleTask => "toggle task",
+            Action::ChangeOrder(_) => "reorder task",
+            Action::MoveBoard(_) => "move task",
         }
-        Action::ChangeOrder(_) => todo!(),
-        Action::MoveBoard(board_uuid) => {
-            format!(
-                "{{\"task_uuid\": \"{}\", \"action\": \"MoveBoard\", \"board_uuid\": \"{}\"}}",
-                task_uuid, board_uuid
-            )
+    }
+}
+
+/// Moves the selected task up (`-1`) or down (`1`) in the task list.
+///
+/// The listed tasks are renumbered, only the ones whose order changed are patched.
+pub async fn reorder_task(app: &mut App, offset: isize) {
+    let Some(index) = app.tasks.state.selected() else {
+        return;
+    };
+    let target = index as isize + offset;
+    if index >= app.tasks.items.len() || target < 0 || target as usize >= app.tasks.items.len() {
+        return;
+    }
+    let target = target as usize;
+
+    let mut listed: Vec<Task> = app.tasks.items.clone();
+    listed.swap(index, target);
+
+    for (order, task) in listed.iter().enumerate() {
+        if task.order != order as i32 {
+            patch_task_by_uuid(app, &task.uuid, Action::ChangeOrder(order as i32)).await;
         }
+    }
+
+    app.tasks.items = listed;
+    app.tasks.state.select(Some(target));
+}
+
+/// Applies the action to the selected task and sends it to the api.
+pub async fn patch_task(app: &mut App, action: Action) {
+    let task_uuid = match app.tasks.selected() {
+        Some(task) => task.uuid.clone(),
+        None => return,
+    };
+
+    patch_task_by_uuid(app, &task_uuid, action).await;
+}
+
+/// Applies the action to the task and sends it to the api.
+pub async fn patch_task_by_uuid(app: &mut App, task_uuid: &str, action: Action) {
+    let before = match app.task_data.iter().find(|task| task.uuid == task_uuid) {
+        Some(task) => task.clone(),
+        None => return,
     };
 
-    // Spawn the async task
-    tokio::spawn(async move {
-        let url = format!("{}/patch/task?device_identifier=tty", API_URL);
-
-        let _response = client
-            .patch(&url)
-            .header("Content-Type", "application/json")
-            .body(body)
-            .send()
-            .await
-            .unwrap()
-            .text()
-            .await
-            .unwrap();
-    });
+    app.apply(Mutation::PatchTask { before, action });
 }
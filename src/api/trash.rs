@@ -0,0 +1,139 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::board::{ensure_board, BoardKind, Boards};
+use crate::api::mutation::Mutation;
+use crate::api::task::{patch_task, patch_task_by_uuid, Action};
+use crate::app::App;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Where a trashed task came from, kept locally since the api doesn't remember it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrashEntry {
+    pub board_uuid: String,
+    /// Unix timestamp in seconds.
+    pub trashed_at: u64,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// Moves the selected task to the trash, remembering its board for [`restore_task`].
+pub async fn trash_task(app: &mut App) {
+    let Some(task) = app.tasks.selected().cloned() else {
+        return;
+    };
+    let Some(trash_uuid) = ensure_board(app, BoardKind::Trash) else {
+        return;
+    };
+
+    app.trash.insert(
+        task.uuid.clone(),
+        TrashEntry {
+            board_uuid: task.board_uuid.clone(),
+            trashed_at: now(),
+        },
+    );
+    patch_task(app, Action::MoveBoard(trash_uuid)).await;
+}
+
+/// Moves the selected trashed task back to the board it was deleted from.
+///
+/// Falls back to the inbox when that board is gone or the task was trashed elsewhere.
+pub async fn restore_task(app: &mut App) {
+    let Some(task) = app.tasks.selected().cloned() else {
+        return;
+    };
+    if app.boards.items.by_uuid(&task.board_uuid).map(|b| b.kind) != Some(BoardKind::Trash) {
+        return;
+    }
+
+    let origin = app
+        .trash
+        .remove(&task.uuid)
+        .map(|entry| entry.board_uuid)
+        .filter(|uuid| app.boards.items.by_uuid(uuid).is_some());
+    let Some(board_uuid) = origin.or_else(|| ensure_board(app, BoardKind::Inbox)) else {
+        return;
+    };
+
+    patch_task_by_uuid(app, &task.uuid, Action::MoveBoard(board_uuid)).await;
+}
+
+/// Permanently deletes the task, if it's still in the trash.
+pub async fn delete_trashed_task(app: &mut App, task_uuid: &str) {
+    let Some(task) = app
+        .task_data
+        .iter()
+        .find(|task| task.uuid == task_uuid)
+        .cloned()
+    else {
+        return;
+    };
+    if app.boards.items.by_uuid(&task.board_uuid).map(|b| b.kind) != Some(BoardKind::Trash) {
+        return;
+    }
+
+    app.apply(Mutation::DeleteTask(task));
+}
+
+/// Permanently deletes every task in the trash.
+pub async fn empty_trash(app: &mut App) {
+    let Some(trash_uuid) = app
+        .boards
+        .items
+        .of_kind(BoardKind::Trash)
+        .map(|board| board.uuid.clone())
+    else {
+        return;
+    };
+
+    let trashed: Vec<_> = app
+        .task_data
+        .iter()
+        .filter(|task| task.board_uuid == trash_uuid)
+        .cloned()
+        .collect();
+
+    for task in trashed {
+        app.trash.remove(&task.uuid);
+        app.apply(Mutation::DeleteTask(task));
+    }
+}
+
+/// Permanently deletes tasks that have been in the trash for more than `days` days.
+///
+/// Tasks trashed from another device have no [`TrashEntry`] and are left alone.
+pub async fn purge_trash(app: &mut App, days: u64) {
+    let Some(trash_uuid) = app
+        .boards
+        .items
+        .of_kind(BoardKind::Trash)
+        .map(|board| board.uuid.clone())
+    else {
+        return;
+    };
+    let cutoff = now().saturating_sub(days.saturating_mul(SECONDS_PER_DAY));
+
+    let expired: Vec<_> = app
+        .task_data
+        .iter()
+        .filter(|task| task.board_uuid == trash_uuid)
+        .filter(|task| {
+            app.trash
+                .get(&task.uuid)
+                .is_some_and(|entry| entry.trashed_at < cutoff)
+        })
+        .cloned()
+        .collect();
+
+    for task in expired {
+        app.trash.remove(&task.uuid);
+        app.apply(Mutation::DeleteTask(task));
+    }
+}
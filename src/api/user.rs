@@ -1,111 +1,78 @@
-use std::fs;
-
 use serde::Serialize;
 
 use serde::Deserialize;
 
+use crate::api::error::ApiError;
+use crate::api::request::CreateUser;
 use crate::app::App;
-use crate::helper::input::InputContentVariants;
-use crate::helper::stateful_list::StatefulList;
-use crate::API_URL;
+use crate::cache::Cache;
+use crate::helper::functions::load_user_data;
+use crate::session::Session;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct User {
     pub uuid: String,
     pub name: String,
     pub email: String,
+    /// Only read from the api, never written to the cache.
+    #[serde(default, skip_serializing)]
     pub password: String,
+    #[serde(default, skip_serializing)]
     pub salt: String,
 }
 
 impl User {}
 
-//TODO: Error handling
-pub async fn sign_in(
-    app: &mut App,
-    credentials: Credentials,
-    cookie: Option<String>,
-) -> Option<User> {
+/// Logs in with email and password, loads the user's data and remembers the session.
+pub async fn sign_in(app: &mut App, credentials: Credentials) -> Result<User, ApiError> {
     if credentials.email.is_empty() || credentials.password.is_empty() {
-        None
-    } else if credentials.email == "cookie" && credentials.password == "cookie" {
-        let url = format!("{}/login/cookie/cookie?device_identifier=tty", API_URL);
-
-        let cookie_formated = if cookie.clone().is_some() {
-            println!("cookie: {}", cookie.clone().unwrap());
-            format!("user_uuid={}", cookie.unwrap())
-        } else {
-            println!("cookie: None");
-            "".to_string()
-        };
-
-        let response: String;
-
-        if cookie_formated.is_empty() {
-            response = app
-                .reqwest_client
-                .get(&url)
-                .send()
-                .await
-                .unwrap()
-                .text()
-                .await
-                .unwrap();
-        } else {
-            response = app
-                .reqwest_client
-                .get(&url)
-                .header("Cookie", cookie_formated)
-                .send()
-                .await
-                .unwrap()
-                .text()
-                .await
-                .unwrap();
-        };
-
-        let user: User = serde_json::from_str(&response).unwrap();
-
-        app.task_data = crate::api::task::get_all_tasks(app).await.unwrap();
-        app.tasks.next();
-        app.boards =
-            StatefulList::with_items(crate::api::board::get_all_user_boards(app).await.unwrap());
-        app.boards.next();
-
-        return Some(user);
-    } else {
-        let client = &app.reqwest_client;
-        let url = format!(
-            "{}/login/{}/{}?device_identifier=tty",
-            API_URL, credentials.email, credentials.password
-        );
-        let response = client.get(&url).send().await.unwrap().text().await.unwrap();
-
-        if response == "\"Logged in\"" {
-            let url = format!("{}/login/cookie/cookie?device_identifier=tty", API_URL);
-
-            let response = client.get(&url).send().await.unwrap().text().await.unwrap();
-
-            let user: User = serde_json::from_str(&response).unwrap();
-
-            app.task_data = crate::api::task::get_all_tasks(app).await.unwrap();
-            app.tasks.next();
-            app.boards = StatefulList::with_items(
-                crate::api::board::get_all_user_boards(app).await.unwrap(),
-            );
-            app.boards.next();
-
-            // save the cookie to ~/.config/quicktick-tty/cookie.txt
-            let cookie_dir = dirs::config_dir().unwrap().join("quicktick-tty");
-            let cookie_file = cookie_dir.join("cookie.txt");
-            fs::create_dir_all(cookie_dir).unwrap();
-            fs::write(cookie_file, user.uuid.clone()).unwrap();
-
-            Some(user)
-        } else {
-            None
+        return Err(ApiError::InvalidCredentials);
+    }
+
+    app.client.login(&credentials).await?;
+    let user = resume_session(app).await?;
+    app.save_session();
+
+    Ok(user)
+}
+
+/// Fetches the user of the session in the client's cookie store and loads their data.
+///
+/// When signing in, offline changes left in the cache by an expired session
+/// are taken over if they're the user's and refused otherwise.
+pub async fn resume_session(app: &mut App) -> Result<User, ApiError> {
+    let user = app.client.current_user().await?;
+    if app.user.is_none() {
+        if let Some(cache) = unsynced_cache(app) {
+            if cache
+                .user
+                .as_ref()
+                .is_some_and(|cached| cached.uuid == user.uuid)
+            {
+                app.load_cache(cache);
+            } else {
+                return Err(unsynced_error(&cache));
+            }
         }
     }
+    load_user_data(app).await?;
+
+    Ok(user)
+}
+
+/// The cache of the profile if it still has changes to send.
+fn unsynced_cache(app: &App) -> Option<Cache> {
+    Cache::load(app.profile.as_deref()).filter(|cache| !cache.queue.is_empty())
+}
+
+fn unsynced_error(cache: &Cache) -> ApiError {
+    ApiError::UnsyncedChanges {
+        email: cache
+            .user
+            .as_ref()
+            .map_or_else(|| "another account".to_string(), |user| user.email.clone()),
+        count: cache.queue.len(),
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -114,71 +81,64 @@ pub struct Credentials {
     pub password: String,
 }
 
-pub async fn sign_up(app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
-    let (email, password, name) = match &app.input_content.variant {
-        InputContentVariants::SignUp {
-            email,
-            password,
-            name,
-        } => (email, password, name),
-        _ => {
-            return Err("Invalid input content variant".into());
-        }
-    };
-
-    let client = &app.reqwest_client;
-    let url = format!("{}/post/create_user?device_identifier=tty", API_URL);
-    let body = format!(
-        "{{\"email\":\"{}\",\"password\":\"{}\",\"name\":\"{}\"}}",
-        email, password, name
-    );
-    let _response = client
-        .post(&url)
-        .header("Content-Type", "application/json")
-        .body(body)
-        .send()
-        .await
-        .unwrap()
-        .text()
-        .await
-        .unwrap();
-
-    app.credentials = Some(Credentials {
-        email: "cookie".to_string(),
-        password: "cookie".to_string(),
-    });
-
-    let user_acc = sign_in(app, app.credentials.clone().unwrap(), None).await;
-
-    if user_acc.is_none() {
-        return Err("Failed to sign in".into());
-    } else {
-        app.user = user_acc;
+pub async fn sign_up(
+    app: &mut App,
+    email: String,
+    password: String,
+    name: String,
+) -> Result<User, ApiError> {
+    // A new account can't take over the changes.
+    if let Some(cache) = unsynced_cache(app) {
+        return Err(unsynced_error(&cache));
     }
 
-    Ok(())
+    app.client
+        .create_user(&CreateUser {
+            email: &email,
+            password: &password,
+            name: &name,
+        })
+        .await?;
+    let user = resume_session(app).await?;
+    app.save_session();
+
+    Ok(user)
 }
 
-pub async fn log_out(app: &mut App) {
-    let cookie_dir = dirs::config_dir().unwrap().join("quicktick-tty");
-    let cookie_file = cookie_dir.join("cookie.txt");
-    fs::remove_file(cookie_file).unwrap();
+/// Forgets the session and the cached data, refused while changes wait to be synced.
+pub async fn log_out(app: &mut App) -> Result<(), ApiError> {
+    // Logging out drops the queue with the cache.
+    if !app.queue.is_empty() {
+        app.notify_error(format!(
+            "{} changes aren't synced yet, press r to retry before logging out",
+            app.queue.len()
+        ));
+        return Ok(());
+    }
+    // A request that fails offline is queued, it would be replayed for the
+    // next account.
+    if app.replaying || app.in_flight > 0 {
+        app.notify_error("Wait for pending changes to be sent before logging out");
+        return Ok(());
+    }
+
+    if let Err(err) = Session::remove(app.profile.as_deref()) {
+        app.notify_error(format!("Couldn't remove the session: {}", err));
+    }
 
-    app.reqwest_client
-        .get(&format!("{}/logout?device_identifier=tty", API_URL))
-        .send()
-        .await
-        .unwrap();
+    let result = app.client.logout().await;
+    if let Err(err) = Cache::remove(app.profile.as_deref()) {
+        app.notify_error(format!("Couldn't remove the cache: {}", err));
+    }
 
-    app.user = None;
+    app.clear_user_data();
     app.credentials = Some(Credentials {
         email: "".to_string(),
         password: "".to_string(),
     });
-    app.boards = StatefulList::with_items(Vec::new());
-    app.tasks = StatefulList::with_items(Vec::new());
-    app.task_data = Vec::new();
     app.input_content.visible = false;
     app.input_content.selected_input = 0;
     app.selected_widget = true;
+
+    result
 }
@@ -1,36 +1,85 @@
 use crate::api::board::Board;
+use crate::api::client::QuickTickClient;
+use crate::api::error::ApiError;
+use crate::api::mutation::{self, Mutation, ReplayOutcome};
 use crate::api::task::Task;
+use crate::api::trash::TrashEntry;
 use crate::api::user::{Credentials, User};
+use crate::cache::Cache;
+use crate::config::Config;
+use crate::event::Event;
 use crate::helper::input::InputContent;
+use crate::helper::popup::Popup;
 use crate::helper::stateful_list::StatefulList;
+use crate::history::History;
+use crate::session::Session;
+use crate::DEFAULT_API_URL;
+use std::collections::HashMap;
 use std::error;
+use std::fmt::Display;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
 
 /// Application result type.
 pub type AppResult<T> = std::result::Result<T, Box<dyn error::Error>>;
 
+/// How long a notification stays in the navbar.
+const NOTIFICATION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often the offline queue is retried.
+const REPLAY_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Message shown in the navbar.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub message: String,
+    pub is_error: bool,
+    pub created_at: Instant,
+}
+
 /// Application.
 #[derive(Debug)]
 pub struct App {
-    pub reqwest_client: reqwest::Client,
+    pub client: QuickTickClient,
     pub running: bool,
     pub user: Option<User>,
     pub credentials: Option<Credentials>,
     pub boards: StatefulList<Board>,
     pub tasks: StatefulList<Task>,
     pub task_data: Vec<Task>,
+    /// The tasks as the server had them at the last sync, see [`crate::sync::merge`].
+    pub base_tasks: Vec<Task>,
+    /// Origin of the trashed tasks by task uuid, see [`crate::api::trash::restore_task`].
+    pub trash: HashMap<String, TrashEntry>,
     pub selected_widget: bool,
     pub input_content: InputContent,
+    pub popup: Option<Popup>,
+    pub config: Config,
+    pub notification: Option<Notification>,
+    /// Where background requests report back to, see [`Event::Mutation`].
+    pub event_sender: Option<mpsc::Sender<Event>>,
+    /// Set when the api can't be reached, mutations are queued instead of sent.
+    pub offline: bool,
+    /// Mutations waiting to be sent to the api, oldest first.
+    pub queue: Vec<Mutation>,
+    /// Whether the queue is being replayed right now.
+    pub replaying: bool,
+    /// Mutations sent in the background that haven't reported back yet.
+    pub in_flight: usize,
+    pub last_replay: Option<Instant>,
+    pub history: History,
+    /// Passphrase the session file is encrypted with, see [`Session::save`].
+    pub passphrase: Option<String>,
+    /// Active profile, `None` for the default one, see [`crate::config::profile_dir`].
+    pub profile: Option<String>,
+    /// The `--server` flag, it keeps precedence when switching profiles.
+    pub cli_server: Option<String>,
 }
 
 impl Default for App {
     fn default() -> Self {
         Self {
-            reqwest_client: reqwest::ClientBuilder::new()
-                .user_agent("quicktick-tty")
-                .cookie_store(true)
-                .timeout(std::time::Duration::from_secs(5))
-                .build()
-                .unwrap(),
+            client: QuickTickClient::new(DEFAULT_API_URL.to_string()),
             running: true,
             user: None,
             credentials: Some(Credentials {
@@ -38,10 +87,25 @@ impl Default for App {
                 password: "".to_string(),
             }),
             task_data: Vec::new(),
+            base_tasks: Vec::new(),
+            trash: HashMap::new(),
             tasks: StatefulList::with_items(Vec::new()),
             boards: StatefulList::with_items(Vec::new()),
             selected_widget: false,
             input_content: InputContent::default(),
+            popup: None,
+            config: Config::default(),
+            notification: None,
+            event_sender: None,
+            offline: false,
+            queue: Vec::new(),
+            replaying: false,
+            in_flight: 0,
+            last_replay: None,
+            history: History::default(),
+            passphrase: None,
+            profile: None,
+            cli_server: None,
         }
     }
 }
@@ -52,8 +116,227 @@ impl App {
         Self::default()
     }
 
+    /// Constructs a new instance of [`App`] talking to the given api.
+    pub fn with_config(config: Config, api_url: String) -> Self {
+        Self {
+            client: QuickTickClient::new(api_url),
+            config,
+            ..Self::default()
+        }
+    }
+
     /// Handles the tick event of the terminal.
-    pub fn tick(&self) {}
+    pub fn tick(&mut self) {
+        if self
+            .notification
+            .as_ref()
+            .is_some_and(|notification| notification.created_at.elapsed() > NOTIFICATION_TIMEOUT)
+        {
+            self.notification = None;
+        }
+
+        // A new sync would replace the merge dialog and the choices made in it.
+        if self.offline
+            && !self.replaying
+            && !matches!(self.popup, Some(Popup::Merge(_)))
+            && self
+                .last_replay
+                .is_none_or(|last| last.elapsed() > REPLAY_INTERVAL)
+        {
+            self.sync();
+        }
+    }
+
+    /// Shows an informational message in the navbar.
+    pub fn notify(&mut self, message: impl Display) {
+        self.notification = Some(Notification {
+            message: message.to_string(),
+            is_error: false,
+            created_at: Instant::now(),
+        });
+    }
+
+    /// Shows an error in the navbar.
+    pub fn notify_error(&mut self, error: impl Display) {
+        self.notification = Some(Notification {
+            message: error.to_string(),
+            is_error: true,
+            created_at: Instant::now(),
+        });
+    }
+
+    /// Applies the mutation locally and sends it to the api in the background.
+    ///
+    /// The mutation is recorded in the [`History`] so it can be undone.
+    pub fn apply(&mut self, mutation: Mutation) {
+        self.history.record(mutation.clone());
+        mutation.apply(self);
+        self.dispatch(mutation);
+        self.save_cache();
+    }
+
+    /// Sends the mutation to the api, the outcome comes back as [`Event::Mutation`].
+    ///
+    /// While offline or while older mutations are still queued, it gets queued too.
+    pub fn dispatch(&mut self, mutation: Mutation) {
+        if self.offline || self.replaying || !self.queue.is_empty() {
+            self.queue.push(mutation);
+            return;
+        }
+
+        let client = self.client.clone();
+        let sender = self.event_sender.clone();
+        self.in_flight += 1;
+
+        tokio::spawn(async move {
+            let result = mutation.send(&client).await;
+            if let Some(sender) = sender {
+                let _ = sender.send(Event::Mutation(mutation, result));
+            }
+        });
+    }
+
+    /// Handles the outcome of a background request, rolling the change back on failure.
+    pub fn handle_mutation_result(&mut self, mutation: Mutation, result: Result<(), ApiError>) {
+        self.in_flight = self.in_flight.saturating_sub(1);
+        match result {
+            Ok(()) => mutation.apply_to_tasks(&mut self.base_tasks),
+            Err(err) if err.is_transport() => {
+                self.go_offline();
+                self.queue.push(mutation);
+            }
+            Err(err) => {
+                mutation.rollback(self);
+                self.history.forget(&mutation);
+                self.notify_error(format!("Couldn't {}: {}", mutation.describe(), err));
+            }
+        }
+        self.save_cache();
+    }
+
+    /// Switches to offline mode, the queue is retried every [`REPLAY_INTERVAL`].
+    pub fn go_offline(&mut self) {
+        if !self.offline {
+            self.offline = true;
+            self.last_replay = Some(Instant::now());
+            self.notify_error("Offline, changes will be synced once the server is reachable");
+        }
+    }
+
+    /// Sends the queued mutations in the background, reported as [`Event::Replay`].
+    pub fn replay_queue(&mut self) {
+        if self.replaying {
+            return;
+        }
+        self.replaying = true;
+        self.last_replay = Some(Instant::now());
+
+        let client = self.client.clone();
+        let queue = self.queue.clone();
+        let sender = self.event_sender.clone();
+
+        tokio::spawn(async move {
+            let outcome = mutation::replay(client, queue).await;
+            if let Some(sender) = sender {
+                let _ = sender.send(Event::Replay(outcome));
+            }
+        });
+    }
+
+    /// Drops the replayed mutations from the queue.
+    pub fn handle_replay(&mut self, outcome: ReplayOutcome) {
+        self.replaying = false;
+        self.queue
+            .drain(..outcome.results.len().min(self.queue.len()));
+
+        for (mutation, result) in outcome.results {
+            match result {
+                Ok(()) => mutation.apply_to_tasks(&mut self.base_tasks),
+                Err(err) => {
+                    mutation.rollback(self);
+                    self.history.forget(&mutation);
+                    self.notify_error(format!("Couldn't {}: {}", mutation.describe(), err));
+                }
+            }
+        }
+
+        if outcome.unreachable {
+            self.go_offline();
+        } else if !self.queue.is_empty() {
+            // Queued while replaying.
+            self.replay_queue();
+        } else if self.offline {
+            self.offline = false;
+            self.notify("Back online, changes synced");
+        }
+        self.save_cache();
+    }
+
+    /// Handles the background fetch started by [`App::sync`].
+    pub fn handle_fetch_result(&mut self, result: Result<(Vec<Task>, Vec<Board>), ApiError>) {
+        match result {
+            Ok((tasks, boards)) => {
+                self.replaying = false;
+                self.handle_fetched(tasks, boards);
+            }
+            Err(err) if err.is_transport() => {
+                self.replaying = false;
+                self.go_offline();
+            }
+            Err(err) => {
+                self.replaying = false;
+                self.notify_error(err);
+            }
+        }
+    }
+
+    /// Takes over the cached data, used when the api can't be reached at startup.
+    pub fn load_cache(&mut self, cache: Cache) {
+        self.user = cache.user;
+        self.task_data = cache.tasks;
+        self.tasks.next();
+        self.boards = StatefulList::with_items(cache.boards);
+        self.boards.items.sort_by_key(|board| board.order);
+        self.boards.next();
+        self.queue = cache.queue;
+        self.base_tasks = cache.base_tasks;
+        self.trash = cache.trash;
+    }
+
+    /// Forgets the signed in user and their data.
+    pub fn clear_user_data(&mut self) {
+        self.user = None;
+        self.boards = StatefulList::with_items(Vec::new());
+        self.tasks = StatefulList::with_items(Vec::new());
+        self.task_data = Vec::new();
+        self.base_tasks = Vec::new();
+        self.trash = HashMap::new();
+        self.queue = Vec::new();
+        self.history.clear();
+        self.offline = false;
+    }
+
+    /// Writes the current data and queue to the cache file.
+    pub fn save_cache(&mut self) {
+        if self.user.is_none() {
+            return;
+        }
+        if let Err(err) = Cache::from_app(self).save(self.profile.as_deref()) {
+            self.notify_error(format!("Couldn't write the cache: {}", err));
+        }
+    }
+
+    /// Stores the client's session cookies so the next start is signed in.
+    pub fn save_session(&mut self) {
+        let Some(cookies) = self.client.session_cookies() else {
+            return;
+        };
+        if let Err(err) =
+            (Session { cookies }).save(self.profile.as_deref(), self.passphrase.as_deref())
+        {
+            self.notify_error(format!("Couldn't save the session: {}", err));
+        }
+    }
 
     /// Set running to false to quit the application.
     pub fn quit(&mut self) {
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::board::Board;
+use crate::api::mutation::Mutation;
+use crate::api::task::Task;
+use crate::api::trash::TrashEntry;
+use crate::api::user::User;
+use crate::app::App;
+use crate::config::profile_dir;
+use crate::session::write_private;
+
+/// Local copy of the user's data, used when the api can't be reached.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct Cache {
+    pub user: Option<User>,
+    pub boards: Vec<Board>,
+    pub tasks: Vec<Task>,
+    /// Mutations that still have to be sent to the api, oldest first.
+    pub queue: Vec<Mutation>,
+    /// The tasks as the server had them at the last sync.
+    #[serde(default)]
+    pub base_tasks: Vec<Task>,
+    /// Origin of the trashed tasks by task uuid.
+    #[serde(default)]
+    pub trash: HashMap<String, TrashEntry>,
+}
+
+impl Cache {
+    pub fn path(profile: Option<&str>) -> PathBuf {
+        profile_dir(profile).join("cache.json")
+    }
+
+    /// Loads the profile's cache, `None` if there is none or it can't be read.
+    pub fn load(profile: Option<&str>) -> Option<Self> {
+        let content = fs::read_to_string(Self::path(profile)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn from_app(app: &App) -> Self {
+        Self {
+            user: app.user.clone(),
+            boards: app.boards.items.clone(),
+            tasks: app.task_data.clone(),
+            queue: app.queue.clone(),
+            base_tasks: app.base_tasks.clone(),
+            trash: app.trash.clone(),
+        }
+    }
+
+    pub fn save(&self, profile: Option<&str>) -> std::io::Result<()> {
+        fs::create_dir_all(profile_dir(profile))?;
+        // Holds the user uuid, which the api still accepts as a cookie.
+        write_private(
+            &Self::path(profile),
+            serde_json::to_string(self)?.as_bytes(),
+        )
+    }
+
+    pub fn remove(profile: Option<&str>) -> std::io::Result<()> {
+        let path = Self::path(profile);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
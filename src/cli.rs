@@ -0,0 +1,268 @@
+pub mod commands;
+pub mod completions;
+pub mod output;
+
+use std::path::PathBuf;
+
+use completions::{Shell, Values};
+
+use crate::export::ExportFormat;
+use output::{Format, Output, SCHEMA_VERSION};
+
+/// Printed for `--help`.
+pub const USAGE: &str = "\
+Usage: quicktick-tty [options] [command]
+
+Without a command the terminal interface starts.
+
+Commands:
+  add <name> [--board <name>] [--description <text>]
+                              add a task, to the inbox by default
+  list [--board <name>] [output options]
+                              list tasks, of every board by default
+  done <task>                 mark a task as done
+  rm <task>                   move a task to the trash, delete it from there
+  boards [output options]     list boards
+  export <markdown|todo.txt> [--board <name>] [--file <path>]
+                              export a board, or all but the trash, to
+                              stdout or a file
+  import todo.txt <file>      create the tasks of a todo.txt file, and
+                              boards for its +projects
+  completions <bash|zsh|fish> print the shell completion script
+
+Tasks are picked by a prefix of their uuid, as shown by `list`.
+
+Output options:
+  --format <json|tsv|table>   json lines, tab separated values or a table
+  --json                      same as --format json
+  --fields <a,b,..>           fields to print, in order
+                              tasks: uuid,name,description,completed,board,
+                                     board_uuid,user_uuid,order
+                              boards: uuid,name,special,order,tasks,user_uuid
+
+Options:
+  --server <url>              api to talk to
+  --profile <name>            profile to use
+  -h, --help                  show this help
+  --                          end of options, the rest are arguments,
+                              e.g. add -- \"-1 fix bug\"";
+
+/// [`USAGE`] followed by the output schema version.
+pub fn usage() -> String {
+    format!("{}\n\nOutput schema version {}", USAGE, SCHEMA_VERSION)
+}
+
+/// Command line arguments.
+#[derive(Debug, Default, Clone)]
+pub struct Args {
+    /// `--server <url>`, overrides the api base url.
+    pub server: Option<String>,
+    /// `--profile <name>`, the profile to start with.
+    pub profile: Option<String>,
+    /// Runs without the terminal interface when set.
+    pub command: Option<Command>,
+    pub help: bool,
+}
+
+/// Subcommands, see [`USAGE`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Add {
+        name: String,
+        board: Option<String>,
+        description: Option<String>,
+    },
+    List {
+        board: Option<String>,
+        output: Output,
+    },
+    /// Marks the task with the uuid prefix as done.
+    Done {
+        task: String,
+    },
+    /// Trashes the task with the uuid prefix.
+    Rm {
+        task: String,
+    },
+    Boards {
+        output: Output,
+    },
+    Export {
+        format: ExportFormat,
+        board: Option<String>,
+        /// Written to stdout when not set.
+        file: Option<PathBuf>,
+    },
+    /// Creates the tasks in the file.
+    Import {
+        format: ExportFormat,
+        file: PathBuf,
+    },
+    Completions {
+        shell: Shell,
+    },
+    /// Hidden, prints values for the completion scripts from the cache.
+    Complete {
+        values: Values,
+    },
+}
+
+impl Args {
+    /// Parses the arguments, without the program name.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+        let mut positional = Vec::new();
+        let mut board = None;
+        let mut description = None;
+        let mut file = None;
+        let mut format = None;
+        let mut fields = None;
+
+        while let Some(arg) = args.next() {
+            // Everything after `--` is positional, for names starting with `-`.
+            if arg == "--" {
+                positional.extend(args.by_ref());
+                break;
+            }
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = |expects: &str| {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or(format!("{} expects {}", flag, expects))
+            };
+
+            match flag.as_str() {
+                "--server" => parsed.server = Some(value("a url")?),
+                "--profile" => parsed.profile = Some(value("a name")?),
+                "--board" => board = Some(value("a board name")?),
+                "--description" => description = Some(value("a description")?),
+                "--file" => file = Some(PathBuf::from(value("a path")?)),
+                "--json" => format = Some(Format::Json),
+                "--format" => format = Some(value("json, tsv or table")?.parse()?),
+                "--fields" => fields = Some(parse_fields(&value("a list of fields")?)?),
+                "-h" | "--help" => parsed.help = true,
+                other if other.starts_with('-') && other.len() > 1 => {
+                    return Err(format!("unknown argument: {}", other))
+                }
+                _ => positional.push(flag),
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        let Some(command) = positional.next() else {
+            return Ok(parsed);
+        };
+        let has_output = format.is_some() || fields.is_some();
+        let mut output = Some(Output {
+            format: format.unwrap_or_default(),
+            fields,
+        });
+        let mut argument = |name: &str| {
+            positional
+                .next()
+                .ok_or(format!("{} expects {}", command, name))
+        };
+
+        let command = match command.as_str() {
+            "add" => Command::Add {
+                name: argument("a task name")?,
+                board: board.take(),
+                description: description.take(),
+            },
+            "list" => Command::List {
+                board: board.take(),
+                output: output.take().unwrap_or_default(),
+            },
+            "done" => Command::Done {
+                task: argument("a task uuid")?,
+            },
+            "rm" => Command::Rm {
+                task: argument("a task uuid")?,
+            },
+            "boards" => Command::Boards {
+                output: output.take().unwrap_or_default(),
+            },
+            "export" => Command::Export {
+                format: argument("a format")?.parse()?,
+                board: board.take(),
+                file: file.take(),
+            },
+            "import" => Command::Import {
+                format: argument("a format")?.parse()?,
+                file: PathBuf::from(argument("a file")?),
+            },
+            "completions" => Command::Completions {
+                shell: argument("bash, zsh or fish")?.parse()?,
+            },
+            "__complete" => Command::Complete {
+                values: argument("boards, tasks or profiles")?.parse()?,
+            },
+            other => return Err(format!("unknown command: {}", other)),
+        };
+
+        if let Some(extra) = positional.next() {
+            return Err(format!("unexpected argument: {}", extra));
+        }
+        if board.is_some() || description.is_some() {
+            return Err(format!(
+                "{} takes no --board or --description",
+                command.name()
+            ));
+        }
+        if file.is_some() {
+            return Err(format!("{} takes no --file", command.name()));
+        }
+        if has_output && output.is_some() {
+            return Err(format!(
+                "{} takes no --json, --format or --fields",
+                command.name()
+            ));
+        }
+
+        parsed.command = Some(command);
+        Ok(parsed)
+    }
+}
+
+impl Command {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Add { .. } => "add",
+            Command::List { .. } => "list",
+            Command::Done { .. } => "done",
+            Command::Rm { .. } => "rm",
+            Command::Boards { .. } => "boards",
+            Command::Export { .. } => "export",
+            Command::Import { .. } => "import",
+            Command::Completions { .. } => "completions",
+            Command::Complete { .. } => "__complete",
+        }
+    }
+
+    /// Whether the command talks to the api, the others only read local files.
+    pub fn needs_session(&self) -> bool {
+        !matches!(self, Command::Completions { .. } | Command::Complete { .. })
+    }
+}
+
+/// Splits `--fields uuid,name` into the field names.
+fn parse_fields(fields: &str) -> Result<Vec<String>, String> {
+    let fields: Vec<String> = fields
+        .split(',')
+        .map(|field| field.trim().to_string())
+        .collect();
+    if fields.iter().any(String::is_empty) {
+        return Err(format!(
+            "--fields expects names separated by commas: {}",
+            fields.join(",")
+        ));
+    }
+    Ok(fields)
+}
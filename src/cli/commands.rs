@@ -0,0 +1,437 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc;
+
+use tui_input::Input;
+
+use crate::api::board::{create_board, ensure_board, Board, BoardKind, Boards};
+use crate::api::mutation::Mutation;
+use crate::api::task::{create_task, delete_task, patch_task_by_uuid, Action, Task};
+use crate::app::App;
+use crate::cache::Cache;
+use crate::cli::completions::Values;
+use crate::cli::output::{record, Output, Record};
+use crate::cli::Command;
+use crate::event::Event;
+use crate::export::{self, ExportFormat};
+use crate::helper::functions::restore_session;
+use crate::helper::input::InputContentVariants;
+use crate::helper::stateful_list::StatefulList;
+use crate::todo_txt::{self, TodoItem};
+
+type CommandResult = Result<(), Box<dyn Error>>;
+
+/// Runs the command with the active profile's session, without the terminal interface.
+///
+/// The commands go through the same functions as the key bindings, so changes
+/// made while offline are queued and synced later.
+pub async fn run(mut app: App, command: Command) -> CommandResult {
+    match command {
+        Command::Completions { shell } => {
+            print!("{}", shell.script());
+            return Ok(());
+        }
+        Command::Complete { values } => {
+            complete(&app, values);
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let (sender, events) = mpsc::channel();
+    app.event_sender = Some(sender);
+
+    restore_session(&mut app).await;
+    settle(&mut app, &events);
+    if app.user.is_none() {
+        return Err(match app.notification.take() {
+            Some(notification) => notification.message,
+            None => "not logged in, start quicktick-tty without a command to log in".to_string(),
+        }
+        .into());
+    }
+    app.notification = None;
+
+    match command {
+        Command::Add {
+            name,
+            board,
+            description,
+        } => add(&mut app, name, board.as_deref(), description, &events).await?,
+        Command::List { board, output } => list(&app, board.as_deref(), &output)?,
+        Command::Done { task } => done(&mut app, &task).await?,
+        Command::Rm { task } => remove(&mut app, &task).await?,
+        Command::Boards { output } => boards(&app, &output)?,
+        Command::Export {
+            format,
+            board,
+            file,
+        } => export(&app, format, board.as_deref(), file.as_deref())?,
+        Command::Import { format, file } => import(&mut app, format, &file, &events).await?,
+        Command::Completions { .. } | Command::Complete { .. } => {
+            unreachable!("handled without a session")
+        }
+    }
+
+    settle(&mut app, &events);
+    if app.offline && app.queue.is_empty() {
+        eprintln!("The server can't be reached, this is the cached data");
+    } else if app.offline {
+        eprintln!(
+            "The server can't be reached, {} changes will be synced later",
+            app.queue.len()
+        );
+    } else if let Some(notification) = app.notification.take().filter(|n| n.is_error) {
+        return Err(notification.message.into());
+    }
+    if app.popup.is_some() {
+        eprintln!("Some tasks were edited elsewhere too, start quicktick-tty to merge them");
+    }
+    Ok(())
+}
+
+/// Waits until the requests running in the background have reported back.
+fn settle(app: &mut App, events: &mpsc::Receiver<Event>) {
+    while app.in_flight > 0 || app.replaying {
+        let Ok(event) = tokio::task::block_in_place(|| events.recv()) else {
+            return;
+        };
+        match event {
+            Event::Mutation(mutation, result) => app.handle_mutation_result(mutation, result),
+            Event::Replay(outcome) => app.handle_replay(outcome),
+            Event::Fetched(result) => app.handle_fetch_result(result),
+            Event::Tick | Event::Key(_) | Event::Mouse(_) | Event::Resize(_, _) => {}
+        }
+    }
+}
+
+/// The board with the name, ignoring case.
+fn find_board(app: &App, name: &str) -> Result<Board, String> {
+    let matches: Vec<&Board> = app
+        .boards
+        .items
+        .iter()
+        .filter(|board| board.name.eq_ignore_ascii_case(name))
+        .collect();
+
+    match matches.as_slice() {
+        [board] => Ok((*board).clone()),
+        [] => Err(format!("no board named \"{}\"", name)),
+        _ => Err(format!("{} boards are named \"{}\"", matches.len(), name)),
+    }
+}
+
+/// The task whose uuid starts with the prefix.
+fn find_task(app: &App, prefix: &str) -> Result<Task, String> {
+    let matches: Vec<&Task> = app
+        .task_data
+        .iter()
+        .filter(|task| task.uuid.starts_with(prefix))
+        .collect();
+
+    match matches.as_slice() {
+        [task] => Ok((*task).clone()),
+        [] => Err(format!("no task matches {}", prefix)),
+        _ => Err(format!(
+            "{} tasks match {}, use a longer prefix",
+            matches.len(),
+            prefix
+        )),
+    }
+}
+
+/// Selects the board and lists its tasks, like the terminal interface does.
+fn select_board(app: &mut App, board_uuid: &str) {
+    let index = app
+        .boards
+        .items
+        .iter()
+        .position(|board| board.uuid == board_uuid);
+    app.boards.state.select(index);
+
+    let mut tasks: Vec<Task> = app
+        .task_data
+        .iter()
+        .filter(|task| task.board_uuid == board_uuid)
+        .cloned()
+        .collect();
+    tasks.sort_by_key(|task| task.order);
+    app.tasks = StatefulList::with_items(tasks);
+}
+
+fn select_task(app: &mut App, task: &Task) {
+    select_board(app, &task.board_uuid);
+    let index = app.tasks.items.iter().position(|t| t.uuid == task.uuid);
+    app.tasks.state.select(index);
+}
+
+/// First characters of the uuid, enough to pick the task in `done` and `rm`.
+pub fn short_uuid(uuid: &str) -> &str {
+    &uuid[..uuid.len().min(8)]
+}
+
+/// Creates the task, reported once the server has it or it's queued.
+async fn add(
+    app: &mut App,
+    name: String,
+    board: Option<&str>,
+    description: Option<String>,
+    events: &mpsc::Receiver<Event>,
+) -> CommandResult {
+    let board_uuid = match board {
+        Some(name) => find_board(app, name)?.uuid,
+        None => ensure_board(app, BoardKind::Inbox).ok_or("not logged in")?,
+    };
+    select_board(app, &board_uuid);
+
+    app.input_content.variant = InputContentVariants::CreateTask {
+        name: Input::new(name),
+        description: Input::new(description.unwrap_or_default()),
+    };
+    app.input_content
+        .validate()
+        .map_err(|error| error.message)?;
+    let count = app.task_data.len();
+    create_task(app).await;
+    let task = app
+        .task_data
+        .get(count)
+        .cloned()
+        .ok_or("couldn't create the task")?;
+
+    settle(app, events);
+    failed(app)?;
+    if app.queue.contains(&Mutation::CreateTask(task.clone())) {
+        println!("Queued \"{}\" ({})", task.name, short_uuid(&task.uuid));
+    } else {
+        println!("Added \"{}\" ({})", task.name, short_uuid(&task.uuid));
+    }
+    Ok(())
+}
+
+fn list(app: &App, board: Option<&str>, output: &Output) -> CommandResult {
+    let board_uuid = board
+        .map(|name| find_board(app, name))
+        .transpose()?
+        .map(|b| b.uuid);
+    let trash_uuid = app
+        .boards
+        .items
+        .of_kind(BoardKind::Trash)
+        .map(|b| b.uuid.clone());
+    let board_order = |uuid: &str| app.boards.items.iter().position(|b| b.uuid == uuid);
+
+    let mut tasks: Vec<&Task> = app
+        .task_data
+        .iter()
+        .filter(|task| match &board_uuid {
+            Some(uuid) => &task.board_uuid == uuid,
+            // The trash is only listed when asked for.
+            None => Some(&task.board_uuid) != trash_uuid.as_ref(),
+        })
+        .collect();
+    tasks.sort_by_key(|task| (board_order(&task.board_uuid), task.order));
+
+    let records: Vec<_> = tasks
+        .into_iter()
+        .map(|task| {
+            let mut fields = record(task);
+            let board = app.boards.items.by_uuid(&task.board_uuid);
+            fields.insert("board".into(), board.map(|board| board.name.clone()).into());
+            fields
+        })
+        .collect();
+    print!("{}", output.render(Record::Task, &records)?);
+    Ok(())
+}
+
+async fn done(app: &mut App, prefix: &str) -> CommandResult {
+    let task = find_task(app, prefix)?;
+    if task.completed {
+        println!("\"{}\" is already done", task.name);
+        return Ok(());
+    }
+
+    patch_task_by_uuid(app, &task.uuid, Action::ToggleTask).await;
+    println!("Done \"{}\"", task.name);
+    Ok(())
+}
+
+async fn remove(app: &mut App, prefix: &str) -> CommandResult {
+    let task = find_task(app, prefix)?;
+    let in_trash =
+        app.boards.items.by_uuid(&task.board_uuid).map(|b| b.kind) == Some(BoardKind::Trash);
+
+    select_task(app, &task);
+    delete_task(app).await;
+
+    if in_trash {
+        println!("Deleted \"{}\"", task.name);
+    } else {
+        println!("Moved \"{}\" to the trash", task.name);
+    }
+    Ok(())
+}
+
+fn boards(app: &App, output: &Output) -> CommandResult {
+    let records: Vec<_> = app
+        .boards
+        .items
+        .iter()
+        .map(|board| {
+            let mut fields = record(board);
+            let count = app
+                .task_data
+                .iter()
+                .filter(|task| task.board_uuid == board.uuid)
+                .count();
+            fields.insert("tasks".into(), count.into());
+            fields
+        })
+        .collect();
+    print!("{}", output.render(Record::Board, &records)?);
+    Ok(())
+}
+
+fn export(
+    app: &App,
+    format: ExportFormat,
+    board: Option<&str>,
+    file: Option<&Path>,
+) -> CommandResult {
+    let board = board.map(|name| find_board(app, name)).transpose()?;
+    let boards = export::boards_to_export(&app.boards.items, board.as_ref());
+    let content = match format {
+        ExportFormat::Markdown => export::markdown(&boards, &app.task_data),
+        ExportFormat::TodoTxt => todo_txt::export(&boards, &app.task_data),
+    };
+
+    match file {
+        Some(path) => {
+            fs::write(path, content)?;
+            eprintln!("Exported to {}", path.display());
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// Creates the boards, then the tasks and then marks the completed ones as
+/// done, waiting in between so the api sees them in that order.
+async fn import(
+    app: &mut App,
+    format: ExportFormat,
+    path: &Path,
+    events: &mpsc::Receiver<Event>,
+) -> CommandResult {
+    if format != ExportFormat::TodoTxt {
+        return Err("only todo.txt files can be imported".into());
+    }
+
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("couldn't read {}: {}", path.display(), err))?;
+    let items: Vec<TodoItem> = text.lines().filter_map(todo_txt::parse_line).collect();
+    for (index, item) in items.iter().enumerate() {
+        app.input_content.variant = InputContentVariants::CreateTask {
+            name: Input::new(item.name.clone()),
+            description: Input::new(item.description.clone()),
+        };
+        app.input_content
+            .validate()
+            .map_err(|error| format!("task {} \"{}\": {}", index + 1, item.name, error.message))?;
+    }
+
+    let project_board = |app: &App, project: &str| {
+        app.boards
+            .items
+            .iter()
+            .find(|board| todo_txt::project_name(&board.name).eq_ignore_ascii_case(project))
+            .map(|board| board.uuid.clone())
+    };
+    let inbox = ensure_board(app, BoardKind::Inbox).ok_or("not logged in")?;
+    let mut created_boards = 0;
+    for project in items.iter().filter_map(|item| item.project.as_deref()) {
+        if project_board(app, project).is_none() {
+            app.input_content.variant = InputContentVariants::CreateBoard {
+                name: Input::new(project.to_string()),
+            };
+            create_board(app).await;
+            created_boards += 1;
+        }
+    }
+    settle(app, events);
+    failed(app)?;
+
+    let mut completed = Vec::new();
+    for item in &items {
+        let board_uuid = match &item.project {
+            Some(project) => project_board(app, project).ok_or("board wasn't created")?,
+            None => inbox.clone(),
+        };
+        select_board(app, &board_uuid);
+        app.input_content.variant = InputContentVariants::CreateTask {
+            name: Input::new(item.name.clone()),
+            description: Input::new(item.description.clone()),
+        };
+        create_task(app).await;
+        if item.completed {
+            completed.extend(app.task_data.last().map(|task| task.uuid.clone()));
+        }
+    }
+    settle(app, events);
+    failed(app)?;
+
+    for uuid in completed {
+        patch_task_by_uuid(app, &uuid, Action::ToggleTask).await;
+    }
+
+    println!(
+        "Imported {} tasks and created {} boards",
+        items.len(),
+        created_boards
+    );
+    Ok(())
+}
+
+/// The error a background request reported, if any.
+fn failed(app: &mut App) -> CommandResult {
+    match app.notification.take() {
+        Some(notification) if notification.is_error && !app.offline => {
+            Err(notification.message.into())
+        }
+        notification => {
+            app.notification = notification;
+            Ok(())
+        }
+    }
+}
+
+/// Prints completion values from the cache, it has to be quick and quiet so
+/// nothing is fetched.
+fn complete(app: &App, values: Values) {
+    if values == Values::Profiles {
+        for name in app.config.profile_names() {
+            println!("{}", name);
+        }
+        return;
+    }
+
+    let Some(cache) = Cache::load(app.profile.as_deref()) else {
+        return;
+    };
+    match values {
+        Values::Boards => {
+            for board in cache.boards {
+                println!("{}", board.name);
+            }
+        }
+        Values::Tasks => {
+            for task in cache.tasks {
+                let name = task.name.split_whitespace().collect::<Vec<_>>().join(" ");
+                println!("{}\t{}", short_uuid(&task.uuid), name);
+            }
+        }
+        Values::Profiles => {}
+    }
+}
@@ -0,0 +1,57 @@
+use std::str::FromStr;
+
+/// Shells `completions` writes a script for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(shell: &str) -> Result<Self, Self::Err> {
+        match shell {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            other => Err(format!("unknown shell {}, use bash, zsh or fish", other)),
+        }
+    }
+}
+
+impl Shell {
+    /// The completion script, it asks `__complete` for board names, task
+    /// uuids and profiles.
+    pub fn script(self) -> &'static str {
+        match self {
+            Shell::Bash => include_str!("completions/quicktick-tty.bash"),
+            Shell::Zsh => include_str!("completions/quicktick-tty.zsh"),
+            Shell::Fish => include_str!("completions/quicktick-tty.fish"),
+        }
+    }
+}
+
+/// Values the completion scripts ask for with the hidden `__complete` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Values {
+    /// Board names, one per line.
+    Boards,
+    /// Short task uuids, each followed by a tab and the task name.
+    Tasks,
+    Profiles,
+}
+
+impl FromStr for Values {
+    type Err = String;
+
+    fn from_str(values: &str) -> Result<Self, Self::Err> {
+        match values {
+            "boards" => Ok(Values::Boards),
+            "tasks" => Ok(Values::Tasks),
+            "profiles" => Ok(Values::Profiles),
+            other => Err(format!("unknown values {}", other)),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+# bash completion for quicktick-tty
+
+# Prints the board names, task uuids or profiles from the local cache.
+_quicktick_tty_values() {
+    local profile=() i
+    for ((i = 1; i < COMP_CWORD - 1; i++)); do
+        if [[ ${COMP_WORDS[i]} == --profile ]]; then
+            profile=(--profile "${COMP_WORDS[i + 1]}")
+        fi
+    done
+    quicktick-tty "${profile[@]}" __complete "$1" 2>/dev/null
+}
+
+_quicktick_tty() {
+    local cur=${COMP_WORDS[COMP_CWORD]} prev=${COMP_WORDS[COMP_CWORD - 1]}
+    local positionals=() i
+
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        case ${COMP_WORDS[i]} in
+            --server | --profile | --board | --description | --file | --format | --fields) ((i++)) ;;
+            -*) ;;
+            *) positionals+=("${COMP_WORDS[i]}") ;;
+        esac
+    done
+
+    case $prev in
+        --board | --profile)
+            local values=() value kind=boards
+            [[ $prev == --profile ]] && kind=profiles
+            while IFS= read -r value; do
+                [[ $value == "$cur"* ]] && values+=("$(printf '%q' "$value")")
+            done < <(_quicktick_tty_values "$kind")
+            COMPREPLY=("${values[@]}")
+            return
+            ;;
+        --format)
+            COMPREPLY=($(compgen -W "json tsv table" -- "$cur"))
+            return
+            ;;
+        --file)
+            COMPREPLY=($(compgen -f -- "$cur"))
+            return
+            ;;
+        --server | --description | --fields)
+            return
+            ;;
+    esac
+
+    local command=${positionals[0]}
+    if [[ $cur == -* ]]; then
+        local flags="--server --profile --help"
+        case $command in
+            add) flags+=" --board --description" ;;
+            list) flags+=" --board --format --json --fields" ;;
+            boards) flags+=" --format --json --fields" ;;
+            export) flags+=" --board --file" ;;
+        esac
+        COMPREPLY=($(compgen -W "$flags" -- "$cur"))
+        return
+    fi
+
+    if ((${#positionals[@]} == 0)); then
+        COMPREPLY=($(compgen -W "add list done rm boards export import completions" -- "$cur"))
+    elif ((${#positionals[@]} == 1)); then
+        case $command in
+            done | rm)
+                COMPREPLY=($(compgen -W "$(_quicktick_tty_values tasks | cut -f1)" -- "$cur"))
+                ;;
+            export)
+                COMPREPLY=($(compgen -W "markdown todo.txt" -- "$cur"))
+                ;;
+            import)
+                COMPREPLY=($(compgen -W "todo.txt" -- "$cur"))
+                ;;
+            completions)
+                COMPREPLY=($(compgen -W "bash zsh fish" -- "$cur"))
+                ;;
+        esac
+    elif ((${#positionals[@]} == 2)) && [[ $command == import ]]; then
+        COMPREPLY=($(compgen -f -- "$cur"))
+    fi
+}
+
+complete -F _quicktick_tty quicktick-tty
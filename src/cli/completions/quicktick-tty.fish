@@ -0,0 +1,54 @@
+# fish completion for quicktick-tty
+
+# Prints the board names, task uuids or profiles from the local cache.
+function __quicktick_tty_values
+    set -l tokens (commandline -opc)
+    set -l profile
+    set -l i (contains -i -- --profile $tokens)
+    if test -n "$i"; and test $i -lt (count $tokens)
+        set profile --profile $tokens[(math $i + 1)]
+    end
+    quicktick-tty $profile __complete $argv 2>/dev/null
+end
+
+function __quicktick_tty_positionals
+    set -l tokens (commandline -opc)
+    set -e tokens[1]
+    set -l skip 0
+    for token in $tokens
+        if test $skip = 1
+            set skip 0
+            continue
+        end
+        switch $token
+            case --server --profile --board --description --file --format --fields
+                set skip 1
+            case '-*'
+            case '*'
+                echo $token
+        end
+    end
+end
+
+# True when the command is one of the arguments and has no argument yet.
+function __quicktick_tty_using
+    set -l positionals (__quicktick_tty_positionals)
+    test (count $positionals) -eq 1; and contains -- $positionals[1] $argv
+end
+
+complete -c quicktick-tty -f
+complete -c quicktick-tty -n 'test (count (__quicktick_tty_positionals)) -eq 0' -a 'add list done rm boards export import completions'
+complete -c quicktick-tty -n '__quicktick_tty_using done rm' -a '(__quicktick_tty_values tasks)'
+complete -c quicktick-tty -n '__quicktick_tty_using export' -a 'markdown todo.txt'
+complete -c quicktick-tty -n '__quicktick_tty_using import' -a todo.txt
+complete -c quicktick-tty -n 'test (count (__quicktick_tty_positionals)) -eq 2; and contains -- import (__quicktick_tty_positionals)[1]' -F
+complete -c quicktick-tty -n '__quicktick_tty_using completions' -a 'bash zsh fish'
+complete -c quicktick-tty -l server -x -d 'api to talk to'
+complete -c quicktick-tty -l profile -x -a '(__quicktick_tty_values profiles)' -d 'profile to use'
+complete -c quicktick-tty -l board -x -a '(__quicktick_tty_values boards)' -d 'board of the task'
+complete -c quicktick-tty -l description -x -d 'description of the task'
+complete -c quicktick-tty -l file -r -F -d 'file to export to'
+complete -c quicktick-tty -l format -x -a 'json tsv table' -d 'output format'
+complete -c quicktick-tty -l json -d 'same as --format json'
+complete -c quicktick-tty -l fields -x -d 'fields to print'
+complete -c quicktick-tty -s h -l help -d 'show the help'
@@ -0,0 +1,203 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Version of the records printed by `list` and `boards`. Bumped when a field
+/// is renamed, removed or changes its type, new fields may come without a bump.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Fields of a task record, the serialized [`Task`](crate::api::task::Task)
+/// plus the name of its board.
+pub const TASK_FIELDS: [&str; 8] = [
+    "uuid",
+    "name",
+    "description",
+    "completed",
+    "board",
+    "board_uuid",
+    "user_uuid",
+    "order",
+];
+const TASK_DEFAULT_FIELDS: [&str; 4] = ["uuid", "completed", "name", "board"];
+
+/// Fields of a board record, the serialized [`Board`](crate::api::board::Board)
+/// plus its number of tasks.
+pub const BOARD_FIELDS: [&str; 6] = ["uuid", "name", "special", "order", "tasks", "user_uuid"];
+const BOARD_DEFAULT_FIELDS: [&str; 3] = ["uuid", "name", "tasks"];
+
+/// How records are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// One json object per line.
+    Json,
+    /// Tab separated values with a header line.
+    Tsv,
+    /// Aligned columns for reading.
+    #[default]
+    Table,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "json" => Ok(Format::Json),
+            "tsv" => Ok(Format::Tsv),
+            "table" => Ok(Format::Table),
+            other => Err(format!("unknown format {}, use json, tsv or table", other)),
+        }
+    }
+}
+
+/// Kind of record, decides the fields that can be picked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Record {
+    Task,
+    Board,
+}
+
+impl Record {
+    fn fields(self) -> &'static [&'static str] {
+        match self {
+            Record::Task => &TASK_FIELDS,
+            Record::Board => &BOARD_FIELDS,
+        }
+    }
+
+    fn default_fields(self) -> &'static [&'static str] {
+        match self {
+            Record::Task => &TASK_DEFAULT_FIELDS,
+            Record::Board => &BOARD_DEFAULT_FIELDS,
+        }
+    }
+}
+
+/// `--format` and `--fields` of `list` and `boards`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Output {
+    pub format: Format,
+    /// Picked fields in order, `None` prints every field as json and the
+    /// default ones otherwise.
+    pub fields: Option<Vec<String>>,
+}
+
+impl Output {
+    /// Renders the records, one per line.
+    pub fn render(&self, kind: Record, records: &[Map<String, Value>]) -> Result<String, String> {
+        let fields: Vec<&str> = match (&self.fields, self.format) {
+            (Some(fields), _) => fields.iter().map(String::as_str).collect(),
+            (None, Format::Json) => kind.fields().to_vec(),
+            (None, _) => kind.default_fields().to_vec(),
+        };
+        if let Some(unknown) = fields.iter().find(|field| !kind.fields().contains(field)) {
+            return Err(format!(
+                "unknown field {}, pick from {}",
+                unknown,
+                kind.fields().join(",")
+            ));
+        }
+
+        let mut output = String::new();
+        match self.format {
+            Format::Json => {
+                for record in records {
+                    let picked: Map<String, Value> = fields
+                        .iter()
+                        .map(|field| (field.to_string(), record_value(record, field)))
+                        .collect();
+                    writeln!(output, "{}", Value::Object(picked)).unwrap();
+                }
+            }
+            Format::Tsv => {
+                writeln!(output, "{}", fields.join("\t")).unwrap();
+                for record in records {
+                    let cells: Vec<String> = fields
+                        .iter()
+                        .map(|field| tsv_cell(&record_value(record, field)))
+                        .collect();
+                    writeln!(output, "{}", cells.join("\t")).unwrap();
+                }
+            }
+            Format::Table => {
+                let mut rows = vec![fields.iter().map(|f| f.to_uppercase()).collect::<Vec<_>>()];
+                rows.extend(records.iter().map(|record| {
+                    fields
+                        .iter()
+                        .map(|field| table_cell(field, &record_value(record, field)))
+                        .collect()
+                }));
+                write_table(&mut output, &rows);
+            }
+        }
+        Ok(output)
+    }
+}
+
+/// The serialized fields of the value.
+pub fn record(value: &impl Serialize) -> Map<String, Value> {
+    match serde_json::to_value(value) {
+        Ok(Value::Object(fields)) => fields,
+        _ => Map::new(),
+    }
+}
+
+fn record_value(record: &Map<String, Value>, field: &str) -> Value {
+    record.get(field).cloned().unwrap_or(Value::Null)
+}
+
+/// Plain text of the value, `\`, tabs and line breaks escaped so every record
+/// stays on one line.
+fn tsv_cell(value: &Value) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    text.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn table_cell(field: &str, value: &Value) -> String {
+    match (field, value) {
+        ("uuid", Value::String(uuid)) => super::commands::short_uuid(uuid).to_string(),
+        ("completed", Value::Bool(completed)) => if *completed { "[x]" } else { "[ ]" }.to_string(),
+        (_, Value::Null) => String::new(),
+        (_, Value::String(text)) => text.lines().collect::<Vec<_>>().join(" "),
+        (_, other) => other.to_string(),
+    }
+}
+
+/// Writes the rows with their columns aligned, the first row is the header.
+fn write_table(output: &mut String, rows: &[Vec<String>]) {
+    let columns = rows.first().map_or(0, Vec::len);
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    for row in rows {
+        let mut line = String::new();
+        for (column, cell) in row.iter().enumerate() {
+            if column > 0 {
+                line.push_str("  ");
+            }
+            line.push_str(cell);
+            if column + 1 < columns {
+                line.extend(std::iter::repeat_n(
+                    ' ',
+                    widths[column] - cell.chars().count(),
+                ));
+            }
+        }
+        writeln!(output, "{}", line.trim_end()).unwrap();
+    }
+}
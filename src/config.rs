@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::sync::ConflictPolicy;
+use crate::DEFAULT_API_URL;
+
+/// Environment variable overriding the api base url.
+pub const SERVER_ENV: &str = "QUICKTICK_SERVER";
+/// Environment variable selecting the profile to use.
+pub const PROFILE_ENV: &str = "QUICKTICK_PROFILE";
+
+/// Returns `~/.config/quicktick-tty`, where the config, session and cache live.
+pub fn config_dir() -> PathBuf {
+    dirs::config_dir().unwrap().join("quicktick-tty")
+}
+
+/// Directory holding the session and cache of a profile, the config dir itself
+/// when no profile is active.
+pub fn profile_dir(profile: Option<&str>) -> PathBuf {
+    match profile {
+        Some(name) => config_dir().join("profiles").join(name),
+        None => config_dir(),
+    }
+}
+
+/// Profile names end up in paths, so only a safe subset is allowed.
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+/// Per-profile overrides of the top level config.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProfileConfig {
+    pub server: Option<String>,
+}
+
+/// Contents of `~/.config/quicktick-tty/config.json`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Config {
+    pub server: Option<String>,
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: HashMap<String, ProfileConfig>,
+    /// How edits made both here and on another device are resolved.
+    #[serde(default)]
+    pub conflict_policy: ConflictPolicy,
+    /// Permanently delete tasks that have been in the trash for this many days.
+    pub trash_auto_purge_days: Option<u64>,
+    /// Delete boards and tasks without asking first.
+    #[serde(default)]
+    pub skip_confirmations: bool,
+    /// Encrypt the stored session with a passphrase.
+    #[serde(default)]
+    pub encrypt_session: bool,
+}
+
+impl Config {
+    pub fn path() -> PathBuf {
+        config_dir().join("config.json")
+    }
+
+    /// Loads the config file, falling back to the defaults when it doesn't exist.
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        serde_json::from_str(&content)
+            .map_err(|err| format!("invalid config {}: {}", path.display(), err).into())
+    }
+
+    /// Name of the active profile, `--profile` flag > `QUICKTICK_PROFILE` > `default_profile`.
+    pub fn profile_name(&self, cli_profile: Option<&str>) -> Option<String> {
+        cli_profile
+            .map(str::to_string)
+            .or_else(|| std::env::var(PROFILE_ENV).ok())
+            .or_else(|| self.default_profile.clone())
+    }
+
+    /// Names of the configured profiles that can be used, sorted.
+    pub fn profile_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .profiles
+            .keys()
+            .filter(|name| is_valid_profile_name(name))
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Resolves the api base url.
+    ///
+    /// Order of precedence: `--server` flag, `QUICKTICK_SERVER`, the profile's
+    /// server, the top level server and finally [`DEFAULT_API_URL`].
+    pub fn api_url(&self, cli_server: Option<&str>, profile: Option<&str>) -> String {
+        let url = cli_server
+            .map(str::to_string)
+            .or_else(|| std::env::var(SERVER_ENV).ok())
+            .or_else(|| {
+                profile
+                    .and_then(|name| self.profiles.get(name))
+                    .and_then(|profile| profile.server.clone())
+            })
+            .or_else(|| self.server.clone())
+            .unwrap_or_else(|| DEFAULT_API_URL.to_string());
+
+        url.trim_end_matches('/').to_string()
+    }
+}
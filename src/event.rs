@@ -1,3 +1,7 @@
+use crate::api::board::Board;
+use crate::api::error::ApiError;
+use crate::api::mutation::{Mutation, ReplayOutcome};
+use crate::api::task::Task;
 use crate::app::AppResult;
 use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
 use std::sync::mpsc;
@@ -5,7 +9,7 @@ use std::thread;
 use std::time::{Duration, Instant};
 
 /// Terminal events.
-#[derive(Clone, Copy, Debug)]
+#[derive(Debug)]
 pub enum Event {
     /// Terminal tick.
     Tick,
@@ -15,6 +19,12 @@ pub enum Event {
     Mouse(MouseEvent),
     /// Terminal resize.
     Resize(u16, u16),
+    /// Background api request finished.
+    Mutation(Mutation, Result<(), ApiError>),
+    /// Offline queue got replayed.
+    Replay(ReplayOutcome),
+    /// Background fetch of the user's tasks and boards finished.
+    Fetched(Result<(Vec<Task>, Vec<Board>), ApiError>),
 }
 
 /// Terminal event handler.
@@ -67,6 +77,11 @@ impl EventHandler {
         }
     }
 
+    /// Returns a sender for reporting events from background tasks.
+    pub fn sender(&self) -> mpsc::Sender<Event> {
+        self.sender.clone()
+    }
+
     /// Receive the next event from the handler thread.
     ///
     /// This function will always block the current thread if
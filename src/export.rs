@@ -0,0 +1,118 @@
+use std::fmt::Write;
+use std::fs;
+use std::str::FromStr;
+
+use crate::api::board::{Board, BoardKind};
+use crate::api::task::Task;
+use crate::app::App;
+
+/// Formats boards can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// A heading per board and a `- [ ]` checklist of its tasks.
+    Markdown,
+    /// A todo.txt line per task, see [`crate::todo_txt`].
+    TodoTxt,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "markdown" | "md" => Ok(ExportFormat::Markdown),
+            "todo.txt" | "todotxt" => Ok(ExportFormat::TodoTxt),
+            other => Err(format!(
+                "unknown format {}, use markdown or todo.txt",
+                other
+            )),
+        }
+    }
+}
+
+/// The board, or every board but the trash.
+pub fn boards_to_export<'a>(boards: &'a [Board], board: Option<&Board>) -> Vec<&'a Board> {
+    boards
+        .iter()
+        .filter(|candidate| match board {
+            Some(board) => candidate.uuid == board.uuid,
+            None => candidate.kind != BoardKind::Trash,
+        })
+        .collect()
+}
+
+/// The boards as markdown checklists, descriptions indented below their task.
+pub fn markdown(boards: &[&Board], tasks: &[Task]) -> String {
+    let mut output = String::new();
+    for (index, board) in boards.iter().enumerate() {
+        if index > 0 {
+            output.push('\n');
+        }
+        writeln!(output, "## {}\n", one_line(&board.name)).unwrap();
+
+        let mut board_tasks: Vec<&Task> = tasks
+            .iter()
+            .filter(|task| task.board_uuid == board.uuid)
+            .collect();
+        board_tasks.sort_by_key(|task| task.order);
+
+        for task in board_tasks {
+            let check = if task.completed { 'x' } else { ' ' };
+            writeln!(output, "- [{}] {}", check, one_line(&task.name)).unwrap();
+            for line in task.description.trim_end().lines() {
+                match line.trim_end() {
+                    "" => output.push('\n'),
+                    line => writeln!(output, "  {}", line).unwrap(),
+                }
+            }
+        }
+    }
+    output
+}
+
+/// Line breaks would end the heading or list item early.
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// File the terminal interface exports to, in the working directory.
+pub fn file_name(board: Option<&Board>) -> String {
+    let Some(board) = board else {
+        return "quicktick.md".to_string();
+    };
+    let slug: String = board
+        .name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "quicktick.md".to_string()
+    } else {
+        format!("quicktick-{}.md", slug)
+    }
+}
+
+/// Writes the selected board, or all boards, as markdown to the working directory.
+pub fn export_markdown(app: &mut App, all: bool) {
+    let board = match all {
+        true => None,
+        false => match app.boards.selected() {
+            Some(board) => Some(board.clone()),
+            None => return,
+        },
+    };
+
+    let boards = boards_to_export(&app.boards.items, board.as_ref());
+    let content = markdown(&boards, &app.task_data);
+    let path = file_name(board.as_ref());
+    match fs::write(&path, content) {
+        Ok(()) => app.notify(format!("Exported to {}", path)),
+        Err(err) => app.notify_error(format!("Couldn't write {}: {}", path, err)),
+    }
+}
@@ -1,8 +1,11 @@
 use std::io::Stderr;
 
-use crate::api::user::sign_in;
+use crate::api::board::{BoardKind, Boards};
+use crate::api::user::{sign_in, sign_up};
 use crate::app::{App, AppResult};
-use crate::helper::input::InputContentVariants;
+use crate::helper::input::{FieldError, InputContentVariants};
+use crate::helper::popup::{ConfirmAction, ConfirmDialog, Popup};
+use crate::sync::Side;
 use crate::tui::Tui;
 use crossterm::event::{
     // Event,
@@ -20,6 +23,13 @@ pub async fn handle_key_events(
     app: &mut App,
     terminal: &mut Tui<CrosstermBackend<Stderr>>,
 ) -> AppResult<()> {
+    app.history.checkpoint();
+
+    if app.popup.is_some() {
+        handle_popup_key_events(key_event, app).await;
+        return Ok(());
+    }
+
     match app.input_content.visible {
         true => match key_event.code {
             KeyCode::Esc => {
@@ -32,29 +42,67 @@ pub async fn handle_key_events(
                 app.input_content.cycle_selected_input();
             }
 
+            KeyCode::Char('r') if key_event.modifiers == KeyModifiers::CONTROL => {
+                app.input_content.toggle_reveal_password();
+            }
+
             KeyCode::Enter => {
+                if let Err(error) = app.input_content.validate() {
+                    app.input_content.selected_input = error.field;
+                    app.input_content.error = Some(error);
+                    return Ok(());
+                }
+                app.input_content.error = None;
+
+                // Errors of the server are shown below the last field.
+                let last_field = app.input_content.input_count() - 1;
                 match &app.input_content.variant {
                     InputContentVariants::LogIn { email, password } => {
                         let credentials = crate::api::user::Credentials {
-                            email: email.to_string(),
+                            email: email.value().trim().to_string(),
                             password: password.to_string(),
                         };
-                        let user_data = sign_in(app, credentials, None).await;
-                        if let Some(user) = user_data {
-                            app.user = Some(user);
-                            app.input_content.visible = false;
-                            app.input_content.selected_input = 0;
-                            terminal.hide_cursor()?;
+                        match sign_in(app, credentials).await {
+                            Ok(user) => {
+                                app.user = Some(user);
+                                app.save_cache();
+                            }
+                            Err(err) => {
+                                app.input_content.error =
+                                    Some(FieldError::new(last_field, err.to_string()));
+                                return Ok(());
+                            }
                         }
                     }
-                    InputContentVariants::SignUp { .. } => {
-                        let user_data = crate::api::user::sign_up(app).await;
-                        if user_data.is_ok() {
-                            app.input_content.visible = false;
-                            app.input_content.selected_input = 0;
-                            terminal.hide_cursor()?;
+                    InputContentVariants::SignUp {
+                        email,
+                        password,
+                        name,
+                        ..
+                    } => {
+                        let (email, password, name) = (
+                            email.value().trim().to_string(),
+                            password.to_string(),
+                            name.value().trim().to_string(),
+                        );
+                        match sign_up(app, email, password, name).await {
+                            Ok(user) => {
+                                app.user = Some(user);
+                                app.save_cache();
+                            }
+                            Err(err) => {
+                                app.input_content.error =
+                                    Some(FieldError::new(last_field, err.to_string()));
+                                return Ok(());
+                            }
                         }
                     }
+                    InputContentVariants::UpdateTask { .. } => {
+                        crate::api::task::update_task(app).await;
+                    }
+                    InputContentVariants::RenameBoard { .. } => {
+                        crate::api::board::rename_board(app).await;
+                    }
                     _ => match app.selected_widget {
                         true => {
                             crate::api::board::create_board(app).await;
@@ -78,10 +126,8 @@ pub async fn handle_key_events(
                     app.quit();
                 }
                 // Exit application on `Ctrl-C`
-                KeyCode::Char('c') => {
-                    if key_event.modifiers == KeyModifiers::CONTROL {
-                        app.quit();
-                    }
+                KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
+                    app.quit();
                 }
 
                 KeyCode::Char('a') => {
@@ -96,6 +142,7 @@ pub async fn handle_key_events(
                         email: Input::default(),
                         name: Input::default(),
                         password: Input::default(),
+                        confirm_password: Input::default(),
                     });
                 }
 
@@ -119,8 +166,26 @@ pub async fn handle_key_events(
                     }
                 }
 
+                KeyCode::Char('J') => {
+                    if app.selected_widget {
+                        crate::api::board::reorder_board(app, 1).await;
+                    } else {
+                        crate::api::task::reorder_task(app, 1).await;
+                    }
+                }
+
+                KeyCode::Char('K') => {
+                    if app.selected_widget {
+                        crate::api::board::reorder_board(app, -1).await;
+                    } else {
+                        crate::api::task::reorder_task(app, -1).await;
+                    }
+                }
+
                 KeyCode::Char('o') => {
-                    crate::api::user::log_out(app).await;
+                    if let Err(err) = crate::api::user::log_out(app).await {
+                        app.notify_error(err);
+                    }
                 }
 
                 KeyCode::Char(' ') => {
@@ -130,28 +195,102 @@ pub async fn handle_key_events(
                     }
                 }
 
+                KeyCode::Char('u') => app.undo(),
+
+                KeyCode::Char('r') if key_event.modifiers == KeyModifiers::CONTROL => app.redo(),
+
                 KeyCode::Char('r') => {
-                    crate::helper::functions::refetch_data(app).await;
+                    if app.offline {
+                        app.sync();
+                    } else {
+                        match crate::helper::functions::refetch_data(app).await {
+                            Ok(()) => {}
+                            Err(err) if err.is_transport() => app.go_offline(),
+                            Err(err) => app.notify_error(err),
+                        }
+                    }
                 }
 
-                KeyCode::Char('i') => {
-                    if app.user.is_some() {
+                KeyCode::Char('i') if app.user.is_some() => {
+                    terminal.show_cursor()?;
+                    match app.selected_widget {
+                        true => app.input_content.show(InputContentVariants::CreateBoard {
+                            name: Input::default(),
+                        }),
+                        false => app.input_content.show(InputContentVariants::CreateTask {
+                            name: Input::default(),
+                            description: Input::default(),
+                        }),
+                    }
+                }
+
+                KeyCode::Char('e') if app.selected_widget => {
+                    if let Some(board) = app.boards.selected() {
+                        let content = InputContentVariants::RenameBoard {
+                            name: Input::new(board.name.clone()),
+                        };
+                        app.input_content.open(content);
                         terminal.show_cursor()?;
-                        match app.selected_widget {
-                            true => app.input_content.show(InputContentVariants::CreateBoard {
-                                name: Input::default(),
-                            }),
-                            false => app.input_content.show(InputContentVariants::CreateTask {
-                                name: Input::default(),
-                                description: Input::default(),
-                            }),
-                        }
                     }
                 }
 
+                KeyCode::Char('e') => {
+                    if let Some(task) = app.tasks.selected() {
+                        let content = InputContentVariants::UpdateTask {
+                            name: Input::new(task.name.clone()),
+                            description: Input::new(task.description.clone()),
+                        };
+                        app.input_content.open(content);
+                        terminal.show_cursor()?;
+                    }
+                }
+
+                KeyCode::Char('m') if !app.selected_widget => {
+                    if let Some(task) = app.tasks.selected() {
+                        // Moving to the trash is deleting, which goes through `d`.
+                        let boards = app
+                            .boards
+                            .items
+                            .iter()
+                            .filter(|board| board.kind != BoardKind::Trash)
+                            .cloned()
+                            .collect();
+                        app.popup = Some(Popup::board_picker(task, boards));
+                    }
+                }
+
+                KeyCode::Char('p') => {
+                    app.popup = Some(Popup::profile_picker(
+                        app.config.profile_names(),
+                        app.profile.as_deref(),
+                    ));
+                }
+
+                KeyCode::Char('x') => crate::export::export_markdown(app, false),
+
+                KeyCode::Char('X') => crate::export::export_markdown(app, true),
+
+                KeyCode::Char('R') if !app.selected_widget => {
+                    crate::api::trash::restore_task(app).await;
+                }
+
+                KeyCode::Char('E') => confirm(app, ConfirmAction::EmptyTrash).await,
+
                 KeyCode::Char('d') => {
                     if app.selected_widget {
-                        crate::api::board::delete_board(app).await;
+                        if let Some(board) = app.boards.selected() {
+                            let uuid = board.uuid.clone();
+                            confirm(app, ConfirmAction::DeleteBoard { uuid }).await;
+                        }
+                    } else if app
+                        .boards
+                        .selected()
+                        .is_some_and(|board| board.kind == BoardKind::Trash)
+                    {
+                        if let Some(task) = app.tasks.selected() {
+                            let uuid = task.uuid.clone();
+                            confirm(app, ConfirmAction::DeleteTask { uuid }).await;
+                        }
                     } else {
                         crate::api::task::delete_task(app).await;
                     }
@@ -163,3 +302,130 @@ pub async fn handle_key_events(
     }
     Ok(())
 }
+
+/// Handles the key events while a [`Popup`] is open.
+async fn handle_popup_key_events(key_event: KeyEvent, app: &mut App) {
+    let Some(popup) = app.popup.as_mut() else {
+        return;
+    };
+
+    match popup {
+        Popup::Merge(dialog) => match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => dialog.conflicts.next(),
+            KeyCode::Char('k') | KeyCode::Up => dialog.conflicts.previous(),
+            KeyCode::Char('l') => dialog.resolve_selected(Side::Local),
+            KeyCode::Char('s') => dialog.resolve_selected(Side::Server),
+            KeyCode::Char('L') => dialog.resolve_all(Side::Local),
+            KeyCode::Char('S') => dialog.resolve_all(Side::Server),
+            KeyCode::Enter => {
+                if dialog.is_resolved() {
+                    let dialog = dialog.clone();
+                    app.popup = None;
+                    app.finish_sync(dialog.pending, &dialog.conflicts.items);
+                } else {
+                    app.notify_error("Pick a side for every conflict first");
+                }
+            }
+            KeyCode::Esc => {
+                app.popup = None;
+                app.notify("Sync postponed, press r to resolve the conflicts");
+            }
+            _ => {}
+        },
+        Popup::BoardPicker { task_uuid, boards } => match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => boards.next(),
+            KeyCode::Char('k') | KeyCode::Up => boards.previous(),
+            KeyCode::Enter => {
+                let task_uuid = task_uuid.clone();
+                let board_uuid = boards.selected().map(|board| board.uuid.clone());
+                app.popup = None;
+                if let Some(board_uuid) = board_uuid {
+                    crate::api::task::move_task(app, &task_uuid, board_uuid).await;
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => app.popup = None,
+            _ => {}
+        },
+        Popup::ProfilePicker(profiles) => match key_event.code {
+            KeyCode::Char('j') | KeyCode::Down => profiles.next(),
+            KeyCode::Char('k') | KeyCode::Up => profiles.previous(),
+            KeyCode::Enter => {
+                let profile = profiles.selected().cloned();
+                app.popup = None;
+                if let Some(profile) = profile {
+                    crate::helper::functions::switch_profile(app, profile).await;
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => app.popup = None,
+            _ => {}
+        },
+        // Only `y`, Enter is too easily hit when the dialog pops up while typing.
+        Popup::Confirm(dialog) => match key_event.code {
+            KeyCode::Char('y') => {
+                let action = dialog.action.clone();
+                app.popup = None;
+                run_confirmed(app, action).await;
+            }
+            KeyCode::Char('n') | KeyCode::Esc | KeyCode::Char('q') => app.popup = None,
+            _ => {}
+        },
+    }
+}
+
+/// Asks for confirmation before running the action, unless the config says not to.
+async fn confirm(app: &mut App, action: ConfirmAction) {
+    if app.config.skip_confirmations {
+        run_confirmed(app, action).await;
+        return;
+    }
+
+    let trash_uuid = app
+        .boards
+        .items
+        .of_kind(BoardKind::Trash)
+        .map(|board| board.uuid.clone());
+    let count_on = |board_uuid: &str| {
+        app.task_data
+            .iter()
+            .filter(|task| task.board_uuid == board_uuid)
+            .count()
+    };
+
+    let message = match &action {
+        ConfirmAction::DeleteBoard { uuid } => {
+            let Some(board) = app.boards.items.by_uuid(uuid) else {
+                return;
+            };
+            format!(
+                "Delete board \"{}\" with {} task(s)?",
+                board.name,
+                count_on(&board.uuid)
+            )
+        }
+        ConfirmAction::DeleteTask { uuid } => {
+            let Some(task) = app.task_data.iter().find(|task| &task.uuid == uuid) else {
+                return;
+            };
+            format!("Permanently delete \"{}\"?", task.name)
+        }
+        ConfirmAction::EmptyTrash => {
+            let count = trash_uuid.as_deref().map_or(0, count_on);
+            if count == 0 {
+                return;
+            }
+            format!("Permanently delete the {} task(s) in the trash?", count)
+        }
+    };
+
+    app.popup = Some(Popup::Confirm(ConfirmDialog { message, action }));
+}
+
+async fn run_confirmed(app: &mut App, action: ConfirmAction) {
+    match action {
+        ConfirmAction::DeleteBoard { uuid } => crate::api::board::delete_board(app, &uuid).await,
+        ConfirmAction::DeleteTask { uuid } => {
+            crate::api::trash::delete_trashed_task(app, &uuid).await
+        }
+        ConfirmAction::EmptyTrash => crate::api::trash::empty_trash(app).await,
+    }
+}
@@ -1,4 +1,4 @@
 pub mod functions;
 pub mod input;
+pub mod popup;
 pub mod stateful_list;
-
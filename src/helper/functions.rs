@@ -1,37 +1,121 @@
 use std::fs;
 
 use crate::{
-    api::{
-        self,
-        user::{sign_in, Credentials},
-    },
+    api::{self, client::QuickTickClient, error::ApiError, user::resume_session},
     app::App,
-    helper::stateful_list::StatefulList,
+    cache::Cache,
+    session::Session,
 };
 
-pub async fn refetch_data(mut app: &mut App) {
-    let config_dir = dirs::config_dir().unwrap().join("quicktick-tty");
-    let cookie_file = config_dir.join("cookie.txt");
-
-    if cookie_file.exists() {
-        let uuid = fs::read_to_string(cookie_file).unwrap();
-        let res = sign_in(
-            app,
-            Credentials {
-                email: "cookie".to_string(),
-                password: "cookie".to_string(),
-            },
-            Some(uuid),
-        )
-        .await;
-
-        if let Some(user) = res {
+/// Fetches the signed in user's tasks and boards and merges them into [`App`].
+pub async fn load_user_data(app: &mut App) -> Result<(), ApiError> {
+    let tasks = api::task::get_all_tasks(app).await?;
+    let boards = api::board::get_all_user_boards(app).await?;
+    app.handle_fetched(tasks, boards);
+    Ok(())
+}
+
+/// Fetches the user again and syncs their data, if signed in.
+///
+/// Refused while changes are being sent, the fetch rewrites the queue they
+/// are drained from.
+pub async fn refetch_data(app: &mut App) -> Result<(), ApiError> {
+    if app.user.is_none() {
+        return Ok(());
+    }
+    if app.replaying || app.in_flight > 0 {
+        app.notify_error("Changes are still being sent, try again in a moment");
+        return Ok(());
+    }
+
+    let user = app.client.current_user().await?;
+    app.user = Some(user);
+    app.save_cache();
+    app.sync();
+    Ok(())
+}
+
+/// Signs in with the active profile's stored session, starting from its cache.
+///
+/// When the server can't be reached the cached data is used offline.
+pub async fn restore_session(app: &mut App) {
+    let profile = app.profile.clone();
+    let session = match Session::load(profile.as_deref(), app.passphrase.as_deref()) {
+        Ok(session) => session,
+        Err(err) => {
+            app.notify_error(format!("Couldn't read the session: {}", err));
+            None
+        }
+    };
+    let cookies = match session {
+        Some(session) => Some(session.cookies),
+        // Older versions stored the user uuid, which the server takes as a cookie.
+        None if profile.is_none() => fs::read_to_string(Session::legacy_path())
+            .ok()
+            .map(|uuid| format!("user_uuid={}", uuid.trim())),
+        None => None,
+    };
+    let Some(cookies) = cookies else {
+        return;
+    };
+    app.client.restore_session(&cookies);
+
+    // Start from the cache, fresh data gets merged into it.
+    if let Some(cache) = Cache::load(profile.as_deref()) {
+        app.load_cache(cache);
+    }
+
+    match resume_session(app).await {
+        Ok(user) => {
             app.user = Some(user);
-            app.task_data = crate::api::task::get_all_tasks(app).await.unwrap();
-            app.tasks.next();
-            app.boards =
-                StatefulList::with_items(api::board::get_all_user_boards(app).await.unwrap());
-            app.boards.next();
+            app.save_cache();
+            app.save_session();
+        }
+        Err(err) if err.is_transport() && app.user.is_some() => app.go_offline(),
+        Err(err) => {
+            // The cache file is kept, its queue is taken over when the user
+            // logs in again, see `resume_session`.
+            let unsynced = app.queue.len();
+            app.clear_user_data();
+            match unsynced {
+                0 => app.notify_error(format!("Couldn't resume the session: {}", err)),
+                n => app.notify_error(format!(
+                    "Couldn't resume the session: {}, log in again to sync {} changes",
+                    err, n
+                )),
+            }
         }
     }
 }
+
+/// Switches to another profile, `None` being the default one.
+///
+/// The current profile's data stays in its cache, queued changes are sent
+/// the next time it's used.
+pub async fn switch_profile(app: &mut App, profile: Option<String>) {
+    if app.profile == profile {
+        return;
+    }
+    // Their results would otherwise land in the new profile's data and queue.
+    if app.replaying || app.in_flight > 0 {
+        app.notify_error("Wait for pending changes to be sent before switching profiles");
+        return;
+    }
+
+    app.save_cache();
+    app.clear_user_data();
+    app.client = QuickTickClient::new(
+        app.config
+            .api_url(app.cli_server.as_deref(), profile.as_deref()),
+    );
+    app.profile = profile;
+    app.input_content.visible = false;
+    app.selected_widget = true;
+    app.notification = None;
+
+    restore_session(app).await;
+    if app.notification.as_ref().is_none_or(|n| !n.is_error) {
+        let name = app.profile.as_deref().unwrap_or("default");
+        app.notify(format!("Switched to profile {}", name));
+    }
+}
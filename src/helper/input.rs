@@ -1,7 +1,10 @@
 use crossterm::event::{Event, KeyEvent};
 use tui::{
     style::{Color, Style},
-    widgets::{Block, BorderType, Borders, Paragraph},
+    widgets::{
+        block::{Position, Title},
+        Block, BorderType, Borders, Paragraph,
+    },
 };
 use tui_input::{backend::crossterm::EventHandler, Input};
 
@@ -21,8 +24,30 @@ pub struct InputContent {
     pub visible: bool,
     pub selected_input: usize,
     pub variant: InputContentVariants,
+    /// Show passwords in plain text instead of masked.
+    pub reveal_password: bool,
+    /// Shown below the field it belongs to until the field is edited.
+    pub error: Option<FieldError>,
+}
+
+/// A problem with the value of a form field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// Index of the field, as in [`InputContent::selected_input`].
+    pub field: usize,
+    pub message: String,
 }
 
+impl FieldError {
+    pub fn new(field: usize, message: impl Into<String>) -> Self {
+        Self {
+            field,
+            message: message.into(),
+        }
+    }
+}
+
+/// The forms, their fields are described in `form_fields!`.
 #[derive(Debug, Clone)]
 pub enum InputContentVariants {
     CreateTask {
@@ -32,6 +57,9 @@ pub enum InputContentVariants {
     CreateBoard {
         name: Input,
     },
+    RenameBoard {
+        name: Input,
+    },
     UpdateTask {
         name: Input,
         description: Input,
@@ -44,63 +72,233 @@ pub enum InputContentVariants {
         email: Input,
         password: Input,
         name: Input,
+        confirm_password: Input,
     },
     ChangeUsername {
         name: Input,
     },
 }
 
-pub enum DataForSubmit {
-    CreateTask {
-        name: String,
-        description: String,
-    },
-    CreateBoard {
-        name: String,
-    },
-    UpdateTask {
-        name: String,
-        description: String,
-    },
-    LogIn {
-        email: String,
-        password: String,
-    },
-    SignUp {
-        email: String,
-        password: String,
-        name: String,
-    },
-    ChangeUsername {
-        name: String,
+/// How a form field is shown and edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormField {
+    pub label: &'static str,
+    /// Maximum number of characters.
+    pub max_len: usize,
+    /// Wraps over [`BIG_INPUT`] lines instead of a single one.
+    pub multiline: bool,
+    /// Rendered masked, see [`InputContent::reveal_password`].
+    pub masked: bool,
+    /// What the value is checked for before the form is submitted.
+    pub check: Check,
+}
+
+/// Rule a form field's value has to follow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    /// Anything goes, empty included.
+    Optional,
+    /// Not blank, the argument names the value in the error.
+    Required(&'static str),
+    Email,
+    /// At least [`MIN_PASSWORD_LEN`] characters.
+    NewPassword,
+    /// Same value as the field with the label, checked while typing too.
+    Matches {
+        label: &'static str,
+        message: &'static str,
     },
 }
 
-impl PartialEq for InputContentVariants {
-    fn eq(&self, other: &Self) -> bool {
-        match self {
-            InputContentVariants::CreateTask { .. } => {
-                matches!(other, InputContentVariants::CreateTask { .. })
+impl Check {
+    /// The problem with the value, `fields` being the whole form.
+    fn error(&self, value: &str, fields: &[(FormField, &Input)]) -> Option<String> {
+        match *self {
+            Check::Optional => None,
+            Check::Required(what) if value.trim().is_empty() => {
+                Some(format!("{} can't be empty", what))
             }
-            InputContentVariants::CreateBoard { .. } => {
-                matches!(other, InputContentVariants::CreateBoard { .. })
+            Check::Email if !is_valid_email(value.trim()) => {
+                Some("Enter a valid email".to_string())
             }
-            InputContentVariants::UpdateTask { .. } => {
-                matches!(other, InputContentVariants::UpdateTask { .. })
+            Check::NewPassword if value.chars().count() < MIN_PASSWORD_LEN => {
+                Some(format!("Use at least {} characters", MIN_PASSWORD_LEN))
             }
-            InputContentVariants::LogIn { .. } => {
-                matches!(other, InputContentVariants::LogIn { .. })
+            Check::Matches { label, message } => fields
+                .iter()
+                .find(|(field, _)| field.label == label)
+                .filter(|(_, other)| other.value() != value)
+                .map(|_| message.to_string()),
+            _ => None,
+        }
+    }
+}
+
+impl FormField {
+    /// Single line field.
+    pub const fn line(label: &'static str) -> Self {
+        Self {
+            label,
+            max_len: WINDOWN_WIDTH * SMALL_INPUT,
+            multiline: false,
+            masked: false,
+            check: Check::Optional,
+        }
+    }
+
+    /// Field wrapping over several lines.
+    pub const fn text_area(label: &'static str) -> Self {
+        Self {
+            label,
+            max_len: WINDOWN_WIDTH * BIG_INPUT,
+            multiline: true,
+            masked: false,
+            check: Check::Optional,
+        }
+    }
+
+    /// Single line field that is masked.
+    pub const fn password(label: &'static str) -> Self {
+        Self {
+            masked: true,
+            ..Self::line(label)
+        }
+    }
+
+    pub const fn check(self, check: Check) -> Self {
+        Self { check, ..self }
+    }
+
+    /// Rows the field takes up, borders included.
+    pub fn height(&self) -> u16 {
+        let lines = if self.multiline {
+            BIG_INPUT
+        } else {
+            SMALL_INPUT
+        };
+        lines as u16 + 2
+    }
+}
+
+const NAME_REQUIRED: Check = Check::Required("The name");
+const SIGN_UP_PASSWORD: &str = "Password (will be encrypted)";
+
+/// The fields of a form with their inputs, in the order they are shown.
+///
+/// Works on `&InputContentVariants` and `&mut InputContentVariants` alike, so
+/// every form is described in this one place.
+macro_rules! form_fields {
+    ($variant:expr) => {
+        match $variant {
+            InputContentVariants::CreateTask { name, description } => vec![
+                (FormField::line("New task Name").check(NAME_REQUIRED), name),
+                (FormField::text_area("Description (optional)"), description),
+            ],
+            InputContentVariants::CreateBoard { name } => {
+                vec![(FormField::line("New board Name").check(NAME_REQUIRED), name)]
             }
-            InputContentVariants::SignUp { .. } => {
-                matches!(other, InputContentVariants::SignUp { .. })
+            InputContentVariants::RenameBoard { name } => {
+                vec![(FormField::line("Rename board").check(NAME_REQUIRED), name)]
             }
-            InputContentVariants::ChangeUsername { .. } => {
-                matches!(other, InputContentVariants::ChangeUsername { .. })
+            InputContentVariants::UpdateTask { name, description } => vec![
+                (
+                    FormField::line("Update task Name").check(NAME_REQUIRED),
+                    name,
+                ),
+                (FormField::text_area("Update task Description"), description),
+            ],
+            InputContentVariants::LogIn { email, password } => vec![
+                (FormField::line("Log in Email").check(Check::Email), email),
+                (
+                    FormField::password("Password").check(Check::Required("The password")),
+                    password,
+                ),
+            ],
+            InputContentVariants::SignUp {
+                email,
+                name,
+                password,
+                confirm_password,
+            } => vec![
+                (FormField::line("Sign up Email").check(Check::Email), email),
+                (FormField::line("Name").check(NAME_REQUIRED), name),
+                (
+                    FormField::password(SIGN_UP_PASSWORD).check(Check::NewPassword),
+                    password,
+                ),
+                (
+                    FormField::password("Confirm password").check(Check::Matches {
+                        label: SIGN_UP_PASSWORD,
+                        message: "The passwords don't match",
+                    }),
+                    confirm_password,
+                ),
+            ],
+            InputContentVariants::ChangeUsername { name } => {
+                vec![(
+                    FormField::line("Change username").check(Check::Required("The username")),
+                    name,
+                )]
             }
         }
+    };
+}
+
+impl InputContentVariants {
+    /// The form's fields and their inputs, in the order they are shown.
+    pub fn fields(&self) -> Vec<(FormField, &Input)> {
+        form_fields!(self)
+    }
+
+    fn fields_mut(&mut self) -> Vec<(FormField, &mut Input)> {
+        form_fields!(self)
+    }
+}
+
+/// Forms are equal when they are the same kind of form, whatever was typed in.
+impl PartialEq for InputContentVariants {
+    fn eq(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
     }
 }
 
+/// Character the password is masked with.
+const MASK: char = '•';
+
+/// The password as it's shown, masked unless `reveal` is set.
+fn password_text(password: &Input, reveal: bool) -> String {
+    if reveal {
+        password.to_string()
+    } else {
+        password.value().chars().map(|_| MASK).collect()
+    }
+}
+
+/// Title of a password field, with a hint for the reveal toggle.
+fn password_title(title: &str, reveal: bool) -> String {
+    format!(
+        "{} (Ctrl-r to {})",
+        title,
+        if reveal { "hide" } else { "show" }
+    )
+}
+
+/// Minimum length of a new account's password.
+pub const MIN_PASSWORD_LEN: usize = 8;
+
+/// Loose email check, the server has the final say.
+pub fn is_valid_email(email: &str) -> bool {
+    let Some((local, domain)) = email.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.contains('@')
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !email.chars().any(char::is_whitespace)
+}
+
 fn line_break_text(text: String) -> String {
     let mut name_to_render = String::new();
 
@@ -115,6 +313,8 @@ fn line_break_text(text: String) -> String {
 
 impl InputContent {
     pub fn show(&mut self, want_to_show: InputContentVariants) {
+        self.reveal_password = false;
+        self.error = None;
         if PartialEq::eq(&self.variant, &want_to_show) {
             self.visible = !self.visible;
         } else {
@@ -123,461 +323,166 @@ impl InputContent {
         }
     }
 
-    pub fn cursor_coordinates(&mut self) -> (u16, u16) // (x, y) x = len of current input, and y is based on selected_input and variant
-    {
-        let y: u16;
-        match self.variant {
-            InputContentVariants::CreateTask { .. } => match self.selected_input {
-                0 => {
-                    y = 1;
-                }
-                1 => {
-                    if self.selected_input_len() <= WINDOWN_WIDTH {
-                        y = 1;
-                    } else if self.selected_input_len() >= WINDOWN_WIDTH
-                        && self.selected_input_len() <= 2 * WINDOWN_WIDTH
-                    {
-                        y = 2;
-                    } else if self.selected_input_len() >= 2 * WINDOWN_WIDTH
-                        && self.selected_input_len() <= 3 * WINDOWN_WIDTH
-                    {
-                        y = 3;
-                    } else {
-                        y = 4;
-                    }
-                }
-                _ => {
-                    y = 1;
-                }
-            },
-            InputContentVariants::UpdateTask { .. } => match self.selected_input {
-                0 => {
-                    y = 1;
-                }
-                1 => {
-                    if self.selected_input_len() <= WINDOWN_WIDTH {
-                        y = 1;
-                    } else if self.selected_input_len() >= WINDOWN_WIDTH
-                        && self.selected_input_len() <= 2 * WINDOWN_WIDTH
-                    {
-                        y = 2;
-                    } else if self.selected_input_len() >= 2 * WINDOWN_WIDTH
-                        && self.selected_input_len() <= 3 * WINDOWN_WIDTH
-                    {
-                        y = 3;
-                    } else {
-                        y = 4;
-                    }
-                }
-                _ => {
-                    y = 1;
-                }
-            },
-            _ => {
-                y = 1;
-            }
-        }
-        ((self.selected_input_len() + 1).try_into().unwrap(), y)
+    /// Shows the given content, replacing any draft of the same variant.
+    pub fn open(&mut self, content: InputContentVariants) {
+        self.variant = content;
+        self.selected_input = 0;
+        self.visible = true;
+        self.reveal_password = false;
+        self.error = None;
     }
 
-    pub fn ui_to_render(&mut self) -> Vec<Paragraph> {
-        let mut to_render = Vec::new();
-        match &self.variant {
-            InputContentVariants::CreateTask { name, description } => {
-                // add \n after every 48 chars
-                to_render.push(
-                    Paragraph::new(name.to_string().clone())
-                        .block(
-                            Block::default()
-                                .title("New task Name")
-                                .borders(Borders::ALL)
-                                .border_type(if self.selected_input == 0 {
-                                    BorderType::Double
-                                } else {
-                                    BorderType::Plain
-                                }),
-                        )
-                        .style(Style::default().bg(Color::Indexed(235)).fg(Color::White)),
-                );
-
-                to_render.push(
-                    Paragraph::new(line_break_text(description.to_string().clone()))
-                        .block(
-                            Block::default()
-                                .title("Description (optinal)")
-                                .borders(Borders::ALL)
-                                .border_type(if self.selected_input == 1 {
-                                    BorderType::Double
-                                } else {
-                                    BorderType::Plain
-                                }),
-                        )
-                        .style(Style::default().bg(Color::Indexed(235)).fg(Color::White)),
-                );
-            }
-            InputContentVariants::CreateBoard { name } => {
-                to_render.push(
-                    Paragraph::new(name.to_string().clone())
-                        .block(
-                            Block::default()
-                                .title("New board Name")
-                                .borders(Borders::ALL)
-                                .border_type(if self.selected_input == 0 {
-                                    BorderType::Double
-                                } else {
-                                    BorderType::Plain
-                                }),
-                        )
-                        .style(Style::default().bg(Color::Indexed(235)).fg(Color::White)),
-                );
-            }
-            InputContentVariants::UpdateTask { name, description } => {
-                to_render.push(
-                    Paragraph::new(name.to_string().clone())
-                        .block(
-                            Block::default()
-                                .title("Update task Name")
-                                .borders(Borders::ALL)
-                                .border_type(if self.selected_input == 0 {
-                                    BorderType::Double
-                                } else {
-                                    BorderType::Plain
-                                }),
-                        )
-                        .style(Style::default().bg(Color::Indexed(235)).fg(Color::White)),
-                );
-                to_render.push(
-                    Paragraph::new(line_break_text(description.to_string().clone()))
-                        .block(
-                            Block::default()
-                                .title("Update task Description")
-                                .borders(Borders::ALL)
-                                .border_type(if self.selected_input == 1 {
-                                    BorderType::Double
-                                } else {
-                                    BorderType::Plain
-                                }),
-                        )
-                        .style(Style::default().bg(Color::Indexed(235)).fg(Color::White)),
-                );
-            }
-            InputContentVariants::LogIn { email, password } => {
-                to_render.push(
-                    Paragraph::new(email.to_string().clone())
-                        .block(
-                            Block::default()
-                                .title("Log in Email")
-                                .borders(Borders::ALL)
-                                .border_type(if self.selected_input == 0 {
-                                    BorderType::Double
-                                } else {
-                                    BorderType::Plain
-                                }),
-                        )
-                        .style(Style::default().bg(Color::Indexed(235)).fg(Color::White)),
-                );
-                to_render.push(
-                    Paragraph::new(password.to_string().clone())
-                        .block(
-                            Block::default()
-                                .title("password")
-                                .borders(Borders::ALL)
-                                .border_type(if self.selected_input == 1 {
-                                    BorderType::Double
-                                } else {
-                                    BorderType::Plain
-                                }),
-                        )
-                        .style(Style::default().bg(Color::Indexed(235)).fg(Color::White)),
-                );
-            }
-            InputContentVariants::SignUp {
-                email,
-                password,
-                name,
-            } => {
-                to_render.push(
-                    Paragraph::new(email.to_string().clone()).block(
-                        Block::default()
-                            .title("Sign up Email")
-                            .borders(Borders::ALL)
-                            .border_type(if self.selected_input == 0 {
-                                BorderType::Double
-                            } else {
-                                BorderType::Plain
-                            })
-                            .style(Style::default().bg(Color::Indexed(235)).fg(Color::White)),
-                    ),
-                );
-                to_render.push(
-                    Paragraph::new(password.to_string().clone())
-                        .block(
-                            Block::default()
-                                .title("Name")
-                                .borders(Borders::ALL)
-                                .border_type(if self.selected_input == 1 {
-                                    BorderType::Double
-                                } else {
-                                    BorderType::Plain
-                                }),
-                        )
-                        .style(Style::default().bg(Color::Indexed(235)).fg(Color::White)),
-                );
-                to_render.push(
-                    Paragraph::new(name.to_string().clone())
-                        .block(
-                            Block::default()
-                                .title("Password (will be encrypted)")
-                                .borders(Borders::ALL)
-                                .border_type(if self.selected_input == 2 {
-                                    BorderType::Double
-                                } else {
-                                    BorderType::Plain
-                                }),
-                        )
-                        .style(Style::default().bg(Color::Indexed(235)).fg(Color::White)),
-                );
-            }
-            InputContentVariants::ChangeUsername { name } => {
-                to_render.push(
-                    Paragraph::new(name.to_string().clone())
-                        .block(
-                            Block::default()
-                                .title("Change username")
-                                .borders(Borders::ALL)
-                                .border_type(if self.selected_input == 0 {
-                                    BorderType::Double
-                                } else {
-                                    BorderType::Plain
-                                }),
-                        )
-                        .style(Style::default().bg(Color::Indexed(235)).fg(Color::White)),
-                );
-            }
-        };
-        to_render
+    pub fn toggle_reveal_password(&mut self) {
+        self.reveal_password = !self.reveal_password;
     }
 
-    pub fn data_for_submit(&mut self) -> Option<DataForSubmit> {
-        match &self.variant {
-            InputContentVariants::CreateTask { name, description } => {
-                Some(DataForSubmit::CreateTask {
-                    name: name.to_string(),
-                    description: description.to_string(),
-                })
+    /// Checks the form before it's submitted, returning the first problem.
+    pub fn validate(&self) -> Result<(), FieldError> {
+        let fields = self.variant.fields();
+        for (index, (field, input)) in fields.iter().enumerate() {
+            if let Some(message) = field.check.error(input.value(), &fields) {
+                return Err(FieldError::new(index, message));
             }
-            InputContentVariants::CreateBoard { name } => Some(DataForSubmit::CreateBoard {
-                name: name.to_string(),
-            }),
-            InputContentVariants::UpdateTask { name, description } => {
-                Some(DataForSubmit::UpdateTask {
-                    name: name.to_string(),
-                    description: description.to_string(),
-                })
+        }
+        Ok(())
+    }
+
+    /// The error to show below the field, fields that have to match another
+    /// one are checked while typing.
+    fn field_error(&self, index: usize) -> Option<String> {
+        if let Some(error) = self.error.as_ref().filter(|error| error.field == index) {
+            return Some(error.message.clone());
+        }
+
+        let fields = self.variant.fields();
+        let (field, input) = fields.get(index)?;
+        // An empty value doesn't count, the user might not have typed it yet.
+        match field.check {
+            Check::Matches { .. } if !input.value().is_empty() => {
+                field.check.error(input.value(), &fields)
             }
-            InputContentVariants::LogIn { email, password } => Some(DataForSubmit::LogIn {
-                email: email.to_string(),
-                password: password.to_string(),
-            }),
-            InputContentVariants::SignUp {
-                email,
-                password,
-                name,
-            } => Some(DataForSubmit::SignUp {
-                email: email.to_string(),
-                password: password.to_string(),
-                name: name.to_string(),
-            }),
-            InputContentVariants::ChangeUsername { name } => Some(DataForSubmit::ChangeUsername {
-                name: name.to_string(),
-            }),
+            _ => None,
         }
     }
 
-    pub fn input_count(&self) -> i32 {
-        match self.variant {
-            InputContentVariants::CreateTask { .. } => 2,
-            InputContentVariants::CreateBoard { .. } => 1,
-            InputContentVariants::UpdateTask { .. } => 2,
-            InputContentVariants::LogIn { .. } => 2,
-            InputContentVariants::SignUp { .. } => 3,
-            InputContentVariants::ChangeUsername { .. } => 1,
+    /// Bordered block of a field, highlighted when selected, with its error at the bottom.
+    fn field_block(&self, field: usize, title: String) -> Block<'static> {
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_type(if self.selected_input == field {
+                BorderType::Double
+            } else {
+                BorderType::Plain
+            });
+
+        match self.field_error(field) {
+            Some(message) => block
+                .title(
+                    Title::from(message)
+                        .position(Position::Bottom)
+                        .alignment(tui::layout::Alignment::Left),
+                )
+                .border_style(Style::default().fg(Color::Red)),
+            None => block,
         }
     }
 
+    /// Cursor position within the selected field's block, `(x, y)` including the border.
+    pub fn cursor_coordinates(&self) -> (u16, u16) {
+        let len = self.selected_input_len();
+        let multiline = self
+            .variant
+            .fields()
+            .get(self.selected_input)
+            .is_some_and(|(field, _)| field.multiline);
+
+        // Multiline text wraps every `WINDOWN_WIDTH` characters, see `line_break_text`.
+        let line = if multiline {
+            len.saturating_sub(1) / WINDOWN_WIDTH
+        } else {
+            0
+        };
+        let column = len - line * WINDOWN_WIDTH;
+        (column as u16 + 1, line as u16 + 1)
+    }
+
+    /// Rows each field takes up, borders included.
+    pub fn field_heights(&self) -> Vec<u16> {
+        self.variant
+            .fields()
+            .iter()
+            .map(|(field, _)| field.height())
+            .collect()
+    }
+
+    pub fn ui_to_render(&self) -> Vec<Paragraph<'static>> {
+        self.variant
+            .fields()
+            .into_iter()
+            .enumerate()
+            .map(|(index, (field, input))| {
+                let (text, title) = if field.masked {
+                    (
+                        password_text(input, self.reveal_password),
+                        password_title(field.label, self.reveal_password),
+                    )
+                } else if field.multiline {
+                    (line_break_text(input.to_string()), field.label.to_string())
+                } else {
+                    (input.to_string(), field.label.to_string())
+                };
+
+                Paragraph::new(text)
+                    .block(self.field_block(index, title))
+                    .style(Style::default().bg(Color::Indexed(235)).fg(Color::White))
+            })
+            .collect()
+    }
+
+    pub fn input_count(&self) -> usize {
+        self.variant.fields().len()
+    }
+
     pub fn cycle_selected_input(&mut self) {
         self.selected_input += 1;
-        if self.selected_input >= self.input_count() as usize {
+        if self.selected_input >= self.input_count() {
             self.selected_input = 0;
         }
     }
 
     pub fn cycle_selected_input_backwards(&mut self) {
         if self.selected_input == 0 {
-            self.selected_input = self.input_count() as usize - 1;
+            self.selected_input = self.input_count() - 1;
         } else {
             self.selected_input -= 1;
         }
     }
 
+    /// Passes the key to the selected field, as long as it has room left.
     pub fn handle_keypress(&mut self, key_event: KeyEvent) {
+        if self
+            .error
+            .as_ref()
+            .is_some_and(|error| error.field == self.selected_input)
+        {
+            self.error = None;
+        }
         let key_event = Event::Key(key_event);
-        match self.variant {
-            InputContentVariants::CreateTask {
-                ref mut name,
-                ref mut description,
-            } => match self.selected_input {
-                0 => {
-                    if name.to_string().len() < WINDOWN_WIDTH * SMALL_INPUT
-                        || key_event == BACKSPACE_KEY_EVENT
-                    {
-                        name.handle_event(&key_event);
-                    }
-                }
-                1 => {
-                    if description.to_string().len() < WINDOWN_WIDTH * BIG_INPUT
-                        || key_event == BACKSPACE_KEY_EVENT
-                    {
-                        description.handle_event(&key_event);
-                    }
-                }
-                _ => {}
-            },
-            InputContentVariants::CreateBoard { ref mut name } => {
-                if let 0 = self.selected_input {
-                    if name.to_string().len() < WINDOWN_WIDTH * SMALL_INPUT
-                        || key_event == BACKSPACE_KEY_EVENT
-                    {
-                        name.handle_event(&key_event);
-                    }
-                }
-            }
-            InputContentVariants::UpdateTask {
-                ref mut name,
-                ref mut description,
-            } => match self.selected_input {
-                0 => {
-                    if name.to_string().len() < WINDOWN_WIDTH * SMALL_INPUT
-                        || key_event == BACKSPACE_KEY_EVENT
-                    {
-                        name.handle_event(&key_event);
-                    }
-                }
-                1 => {
-                    if description.to_string().len() < WINDOWN_WIDTH * BIG_INPUT
-                        || key_event == BACKSPACE_KEY_EVENT
-                    {
-                        description.handle_event(&key_event);
-                    }
-                }
-                _ => {}
-            },
-            InputContentVariants::LogIn {
-                ref mut email,
-                ref mut password,
-            } => match self.selected_input {
-                0 => {
-                    if email.to_string().len() < WINDOWN_WIDTH * SMALL_INPUT
-                        || key_event == BACKSPACE_KEY_EVENT
-                    {
-                        email.handle_event(&key_event);
-                    }
-                }
-                1 => {
-                    if password.to_string().len() < WINDOWN_WIDTH * SMALL_INPUT
-                        || key_event == BACKSPACE_KEY_EVENT
-                    {
-                        password.handle_event(&key_event);
-                    }
-                }
-                _ => {}
-            },
-            InputContentVariants::SignUp {
-                ref mut email,
-                ref mut password,
-                ref mut name,
-            } => match self.selected_input {
-                0 => {
-                    if email.to_string().len() < WINDOWN_WIDTH * SMALL_INPUT
-                        || key_event == BACKSPACE_KEY_EVENT
-                    {
-                        email.handle_event(&key_event);
-                    }
-                }
-                1 => {
-                    if password.to_string().len() < WINDOWN_WIDTH * SMALL_INPUT
-                        || key_event == BACKSPACE_KEY_EVENT
-                    {
-                        password.handle_event(&key_event);
-                    }
-                }
-                2 => {
-                    if name.to_string().len() < WINDOWN_WIDTH * SMALL_INPUT {
-                        name.handle_event(&key_event);
-                    }
-                }
-                _ => {}
-            },
-            InputContentVariants::ChangeUsername { ref mut name } => {
-                if let 0 = self.selected_input {
-                    if name.to_string().len() < WINDOWN_WIDTH * SMALL_INPUT
-                        || key_event == BACKSPACE_KEY_EVENT
-                    {
-                        name.handle_event(&key_event);
-                    }
-                }
+
+        let selected = self.selected_input;
+        if let Some((field, input)) = self.variant.fields_mut().into_iter().nth(selected) {
+            if input.value().chars().count() < field.max_len || key_event == BACKSPACE_KEY_EVENT {
+                input.handle_event(&key_event);
             }
         }
     }
 
-    pub fn selected_input_len(&mut self) -> usize {
-        match self.variant {
-            InputContentVariants::CreateTask {
-                ref name,
-                ref description,
-            } => {
-                if self.selected_input == 0 {
-                    name.to_string().chars().count()
-                } else {
-                    description.to_string().chars().count()
-                }
-            }
-            InputContentVariants::CreateBoard { ref name } => name.to_string().chars().count(),
-            InputContentVariants::UpdateTask {
-                ref name,
-                ref description,
-            } => {
-                if self.selected_input == 0 {
-                    name.to_string().chars().count()
-                } else {
-                    description.to_string().chars().count()
-                }
-            }
-            InputContentVariants::LogIn {
-                ref email,
-                ref password,
-            } => {
-                if self.selected_input == 0 {
-                    email.to_string().chars().count()
-                } else {
-                    password.to_string().chars().count()
-                }
-            }
-            InputContentVariants::SignUp {
-                ref email,
-                ref password,
-                ref name,
-            } => {
-                if self.selected_input == 0 {
-                    email.to_string().chars().count()
-                } else if self.selected_input == 1 {
-                    password.to_string().chars().count()
-                } else {
-                    name.to_string().chars().count()
-                }
-            }
-            InputContentVariants::ChangeUsername { ref name } => name.to_string().chars().count(),
-        }
+    pub fn selected_input_len(&self) -> usize {
+        self.variant
+            .fields()
+            .get(self.selected_input)
+            .map_or(0, |(_, input)| input.value().chars().count())
     }
 }
 
@@ -590,6 +495,8 @@ impl Default for InputContent {
                 name: Input::default(),
                 description: Input::default(),
             },
+            reveal_password: false,
+            error: None,
         }
     }
 }
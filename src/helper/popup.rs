@@ -0,0 +1,109 @@
+use crate::api::board::Board;
+use crate::api::task::Task;
+use crate::helper::stateful_list::StatefulList;
+use crate::sync::{Conflict, PendingSync, Side};
+
+/// Modal dialogs that take over the keyboard until they are closed.
+#[derive(Debug, Clone)]
+pub enum Popup {
+    Merge(MergeDialog),
+    /// Picks the board to move the task to.
+    BoardPicker {
+        task_uuid: String,
+        boards: StatefulList<Board>,
+    },
+    /// Asks before running a destructive action.
+    Confirm(ConfirmDialog),
+    /// Picks the profile to switch to, `None` is the default profile.
+    ProfilePicker(StatefulList<Option<String>>),
+}
+
+impl Popup {
+    /// Board picker for moving `task`, listing `boards` with its board preselected.
+    pub fn board_picker(task: &Task, boards: Vec<Board>) -> Self {
+        let selected = boards
+            .iter()
+            .position(|board| board.uuid == task.board_uuid)
+            .unwrap_or(0);
+        let mut list = StatefulList::with_items(boards);
+        if !list.items.is_empty() {
+            list.state.select(Some(selected));
+        }
+        Popup::BoardPicker {
+            task_uuid: task.uuid.clone(),
+            boards: list,
+        }
+    }
+
+    /// Profile picker listing the default profile and `names`, with `current` preselected.
+    pub fn profile_picker(names: Vec<String>, current: Option<&str>) -> Self {
+        let profiles: Vec<Option<String>> = std::iter::once(None)
+            .chain(names.into_iter().map(Some))
+            .collect();
+        let selected = profiles
+            .iter()
+            .position(|profile| profile.as_deref() == current)
+            .unwrap_or(0);
+        let mut list = StatefulList::with_items(profiles);
+        list.state.select(Some(selected));
+        Popup::ProfilePicker(list)
+    }
+}
+
+/// Lets the user pick a side for every conflicting field of a sync.
+#[derive(Debug, Clone)]
+pub struct MergeDialog {
+    pub conflicts: StatefulList<Conflict>,
+    pub pending: PendingSync,
+}
+
+impl MergeDialog {
+    pub fn new(conflicts: Vec<Conflict>, pending: PendingSync) -> Self {
+        let mut conflicts = StatefulList::with_items(conflicts);
+        conflicts.next();
+        Self { conflicts, pending }
+    }
+
+    /// Resolves the selected conflict and moves on to the next one.
+    pub fn resolve_selected(&mut self, side: Side) {
+        if let Some(conflict) = self.conflicts.selected() {
+            conflict.resolution = Some(side);
+        }
+        self.conflicts.next();
+    }
+
+    pub fn resolve_all(&mut self, side: Side) {
+        for conflict in self.conflicts.items.iter_mut() {
+            conflict.resolution = Some(side);
+        }
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        self.conflicts
+            .items
+            .iter()
+            .all(|conflict| conflict.resolution.is_some())
+    }
+}
+
+/// Destructive actions that ask for confirmation first.
+///
+/// They hold the uuid of their target, the selection can change while the
+/// dialog is open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfirmAction {
+    DeleteBoard {
+        uuid: String,
+    },
+    /// Permanently deletes the task from the trash.
+    DeleteTask {
+        uuid: String,
+    },
+    EmptyTrash,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfirmDialog {
+    pub message: String,
+    pub action: ConfirmAction,
+}
@@ -0,0 +1,122 @@
+use crate::api::mutation::Mutation;
+use crate::app::App;
+
+/// How many steps can be undone.
+const MAX_STEPS: usize = 100;
+
+/// Undo and redo stacks of the applied mutations.
+///
+/// A step holds every mutation applied by one key press, reordering a task
+/// for example patches the order of all its neighbours.
+#[derive(Debug, Default)]
+pub struct History {
+    undo: Vec<Vec<Mutation>>,
+    redo: Vec<Vec<Mutation>>,
+    /// Whether mutations are still added to the last undo step.
+    open: bool,
+}
+
+impl History {
+    /// Starts a new step, called before every key press is handled.
+    pub fn checkpoint(&mut self) {
+        self.open = false;
+    }
+
+    /// Adds a freshly applied mutation to the current step.
+    pub fn record(&mut self, mutation: Mutation) {
+        self.redo.clear();
+        match self.undo.last_mut() {
+            Some(step) if self.open => step.push(mutation),
+            _ => {
+                self.undo.push(vec![mutation]);
+                if self.undo.len() > MAX_STEPS {
+                    self.undo.remove(0);
+                }
+                self.open = true;
+            }
+        }
+    }
+
+    /// Drops a mutation that was rolled back, undoing it would revert a change
+    /// the api never saw.
+    ///
+    /// Only the oldest equal entry is dropped, mutations are answered in the
+    /// order they were sent and an equal one recorded later may still succeed.
+    pub fn forget(&mut self, mutation: &Mutation) {
+        for steps in [&mut self.undo, &mut self.redo] {
+            let found = steps.iter().enumerate().find_map(|(index, step)| {
+                let entry = step.iter().position(|recorded| recorded == mutation)?;
+                Some((index, entry))
+            });
+            if let Some((index, entry)) = found {
+                steps[index].remove(entry);
+                if steps[index].is_empty() {
+                    steps.remove(index);
+                }
+                return;
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl App {
+    /// Reverts the last step by applying the inverse mutations, newest first.
+    pub fn undo(&mut self) {
+        self.history.open = false;
+        let Some(step) = self.history.undo.pop() else {
+            self.notify("Nothing to undo");
+            return;
+        };
+        let message = format!("Undid {}", describe(&step));
+        let inverse = self.apply_inverse(step);
+        self.history.redo.push(inverse);
+        self.notify(message);
+    }
+
+    /// Applies the last undone step again.
+    pub fn redo(&mut self) {
+        self.history.open = false;
+        let Some(step) = self.history.redo.pop() else {
+            self.notify("Nothing to redo");
+            return;
+        };
+        let inverse = self.apply_inverse(step);
+        let message = format!("Redid {}", describe(&inverse));
+        self.history.undo.push(inverse);
+        self.notify(message);
+    }
+
+    /// Applies the inverse of every mutation in the step and returns the
+    /// inverses, so applying them inverted again takes the step back.
+    ///
+    /// They are sent through the queue, which keeps their order, a recreated
+    /// board has to reach the api before its tasks.
+    fn apply_inverse(&mut self, step: Vec<Mutation>) -> Vec<Mutation> {
+        let mut inverse = Vec::new();
+        for mutation in step.iter().rev() {
+            for mutation in mutation.inverse(&self.boards.items, &self.task_data) {
+                mutation.apply(self);
+                self.queue.push(mutation.clone());
+                inverse.push(mutation);
+            }
+        }
+        if !self.offline {
+            self.replay_queue();
+        }
+        self.boards.items.sort_by_key(|board| board.order);
+        self.save_cache();
+        inverse
+    }
+}
+
+fn describe(step: &[Mutation]) -> String {
+    match step {
+        [mutation] => mutation.describe(),
+        [mutation, ..] => format!("{} and {} more", mutation.describe(), step.len() - 1),
+        [] => String::new(),
+    }
+}
@@ -19,4 +19,29 @@ pub mod api;
 /// Helper functions
 pub mod helper;
 
-pub const API_URL: &str = "https://quicktick-api.fly.dev";
+/// Config file and api url resolution.
+pub mod config;
+
+/// Offline cache.
+pub mod cache;
+
+/// Stored login session.
+pub mod session;
+
+/// Undo and redo.
+pub mod history;
+
+/// Merging local changes with the server's.
+pub mod sync;
+
+/// Command line arguments.
+pub mod cli;
+
+/// Exporting boards to markdown and todo.txt.
+pub mod export;
+
+/// Reading and writing todo.txt files.
+pub mod todo_txt;
+
+/// Api url used when neither the config, the environment nor `--server` set one.
+pub const DEFAULT_API_URL: &str = "https://quicktick-api.fly.dev";
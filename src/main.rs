@@ -1,51 +1,60 @@
-use quicktick_tty::api;
-use quicktick_tty::api::user::{sign_in, Credentials};
+use quicktick_tty::api::trash::purge_trash;
 use quicktick_tty::app::{App, AppResult};
+use quicktick_tty::cli::{commands, usage, Args, Command};
+use quicktick_tty::config::{is_valid_profile_name, Config};
 use quicktick_tty::event::{Event, EventHandler};
 use quicktick_tty::handler::handle_key_events;
-use quicktick_tty::helper::stateful_list::StatefulList;
+use quicktick_tty::helper::functions::restore_session;
+use quicktick_tty::session::PASSPHRASE_ENV;
 use quicktick_tty::tui::Tui;
-use std::{fs, io};
+use std::io;
 use tui::backend::CrosstermBackend;
 use tui::Terminal;
 
 #[tokio::main]
 async fn main() -> AppResult<()> {
-    let mut app = App::new();
+    let args = Args::parse(std::env::args().skip(1))?;
+    if args.help {
+        println!("{}", usage());
+        return Ok(());
+    }
+    let config = Config::load()?;
+    let profile = config.profile_name(args.profile.as_deref());
+    if let Some(name) = profile
+        .as_deref()
+        .filter(|name| !is_valid_profile_name(name))
+    {
+        return Err(format!("invalid profile name: {}", name).into());
+    }
+    let api_url = config.api_url(args.server.as_deref(), profile.as_deref());
+    let mut app = App::with_config(config, api_url);
+    app.profile = profile;
+    app.cli_server = args.server;
 
-    // Try to sign in with a cookie
-    let config_dir = dirs::config_dir().unwrap().join("quicktick-tty");
-    let cookie_file = config_dir.join("cookie.txt");
+    if app.config.encrypt_session && args.command.as_ref().is_none_or(Command::needs_session) {
+        app.passphrase = Some(match std::env::var(PASSPHRASE_ENV) {
+            Ok(passphrase) => passphrase,
+            Err(_) => rpassword::prompt_password("Session passphrase: ")?,
+        });
+    }
 
-    if cookie_file.exists() {
-        let uuid = fs::read_to_string(cookie_file).unwrap();
-        println!("Found cookie: {}", uuid);
-        println!("Signing in with cookie...");
-        let res = sign_in(
-            &mut app,
-            Credentials {
-                email: "cookie".to_string(),
-                password: "cookie".to_string(),
-            },
-            Some(uuid),
-        )
-        .await;
+    if let Some(command) = args.command {
+        return commands::run(app, command).await;
+    }
 
-        if let Some(user) = res {
-            app.user = Some(user);
-            app.task_data = crate::api::task::get_all_tasks(&mut app).await.unwrap();
-            app.tasks.next();
-            app.boards =
-                StatefulList::with_items(api::board::get_all_user_boards(&mut app).await.unwrap());
-            app.boards.next();
+    let events = EventHandler::new(250);
+    app.event_sender = Some(events.sender());
 
-            println!("Signed in with cookie!");
-        }
+    // Try to resume the stored session
+    restore_session(&mut app).await;
+
+    if let Some(days) = app.config.trash_auto_purge_days {
+        purge_trash(&mut app, days).await;
     }
+
     // Initialize the terminal user interface.
     let backend = CrosstermBackend::new(io::stderr());
     let terminal = Terminal::new(backend)?;
-    let events = EventHandler::new(250);
     let mut tui = Tui::new(terminal, events);
     tui.init()?;
 
@@ -59,6 +68,9 @@ async fn main() -> AppResult<()> {
             Event::Key(key_event) => handle_key_events(key_event, &mut app, &mut tui).await?,
             Event::Mouse(_) => {}
             Event::Resize(_, _) => {}
+            Event::Mutation(mutation, result) => app.handle_mutation_result(mutation, result),
+            Event::Replay(outcome) => app.handle_replay(outcome),
+            Event::Fetched(result) => app.handle_fetch_result(result),
         }
     }
 
@@ -0,0 +1,199 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::config::{config_dir, profile_dir};
+
+/// Environment variable holding the passphrase the session is encrypted with.
+pub const PASSPHRASE_ENV: &str = "QUICKTICK_PASSPHRASE";
+
+const KEY_ITERATIONS: u32 = 100_000;
+const SALT_LEN: usize = 16;
+
+/// The cookies the server set when logging in.
+#[derive(Debug, Clone)]
+pub struct Session {
+    /// `Cookie` header value, e.g. `session=abc; other=def`.
+    pub cookies: String,
+}
+
+/// Contents of the session file.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+enum StoredSession {
+    Plain {
+        cookies: String,
+    },
+    /// ChaCha20-Poly1305 with a key derived from the passphrase by PBKDF2, fields are base64.
+    Encrypted {
+        salt: String,
+        nonce: String,
+        ciphertext: String,
+    },
+}
+
+#[derive(Debug)]
+pub enum SessionError {
+    Io(std::io::Error),
+    Corrupt,
+    /// The session is encrypted and no passphrase was given.
+    PassphraseRequired,
+    WrongPassphrase,
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionError::Io(err) => write!(f, "{}", err),
+            SessionError::Corrupt => write!(f, "the session file is corrupt"),
+            SessionError::PassphraseRequired => {
+                write!(f, "the session is encrypted, set {}", PASSPHRASE_ENV)
+            }
+            SessionError::WrongPassphrase => write!(f, "wrong passphrase"),
+        }
+    }
+}
+
+impl Error for SessionError {}
+
+impl From<std::io::Error> for SessionError {
+    fn from(err: std::io::Error) -> Self {
+        SessionError::Io(err)
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Key {
+    let mut key = Key::default();
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, KEY_ITERATIONS, &mut key);
+    key
+}
+
+impl Session {
+    pub fn path(profile: Option<&str>) -> PathBuf {
+        profile_dir(profile).join("session.json")
+    }
+
+    /// `cookie.txt` of older versions, holding the plain user uuid.
+    ///
+    /// Older versions had no profiles, so it only belongs to the default one.
+    pub fn legacy_path() -> PathBuf {
+        config_dir().join("cookie.txt")
+    }
+
+    /// Loads the profile's stored session, `None` if there is none.
+    pub fn load(
+        profile: Option<&str>,
+        passphrase: Option<&str>,
+    ) -> Result<Option<Self>, SessionError> {
+        let content = match fs::read_to_string(Self::path(profile)) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        match serde_json::from_str(&content).map_err(|_| SessionError::Corrupt)? {
+            StoredSession::Plain { cookies } => Ok(Some(Self { cookies })),
+            StoredSession::Encrypted {
+                salt,
+                nonce,
+                ciphertext,
+            } => {
+                let passphrase = passphrase.ok_or(SessionError::PassphraseRequired)?;
+                let decode = |field: &str| BASE64.decode(field).map_err(|_| SessionError::Corrupt);
+                let (salt, nonce, ciphertext) =
+                    (decode(&salt)?, decode(&nonce)?, decode(&ciphertext)?);
+                if nonce.len() != 12 {
+                    return Err(SessionError::Corrupt);
+                }
+
+                let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt));
+                let plaintext = cipher
+                    .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+                    .map_err(|_| SessionError::WrongPassphrase)?;
+                let cookies = String::from_utf8(plaintext).map_err(|_| SessionError::Corrupt)?;
+                Ok(Some(Self { cookies }))
+            }
+        }
+    }
+
+    /// Writes the session, readable by the owner only, encrypted when a passphrase is given.
+    ///
+    /// The legacy `cookie.txt` is removed once the default profile's session is saved.
+    pub fn save(
+        &self,
+        profile: Option<&str>,
+        passphrase: Option<&str>,
+    ) -> Result<(), SessionError> {
+        let stored = match passphrase {
+            None => StoredSession::Plain {
+                cookies: self.cookies.clone(),
+            },
+            Some(passphrase) => {
+                let mut salt = [0u8; SALT_LEN];
+                OsRng.fill_bytes(&mut salt);
+                let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+                let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt));
+                let ciphertext = cipher
+                    .encrypt(&nonce, self.cookies.as_bytes())
+                    .map_err(|_| SessionError::Corrupt)?;
+
+                StoredSession::Encrypted {
+                    salt: BASE64.encode(salt),
+                    nonce: BASE64.encode(nonce),
+                    ciphertext: BASE64.encode(ciphertext),
+                }
+            }
+        };
+        let content = serde_json::to_string(&stored).map_err(std::io::Error::from)?;
+
+        fs::create_dir_all(profile_dir(profile))?;
+        write_private(&Self::path(profile), content.as_bytes())?;
+
+        let legacy = Self::legacy_path();
+        if profile.is_none() && legacy.exists() {
+            fs::remove_file(legacy)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the profile's session, and the legacy `cookie.txt` for the default profile.
+    pub fn remove(profile: Option<&str>) -> std::io::Result<()> {
+        let mut paths = vec![Self::path(profile)];
+        if profile.is_none() {
+            paths.push(Self::legacy_path());
+        }
+        for path in paths {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the file with `0600` permissions, also tightening them on an existing file.
+pub(crate) fn write_private(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+
+    options.open(path)?.write_all(content)
+}
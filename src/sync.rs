@@ -0,0 +1,357 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::board::Board;
+use crate::api::mutation::Mutation;
+use crate::api::task::{Action, Task};
+use crate::app::App;
+use crate::event::Event;
+use crate::helper::popup::{MergeDialog, Popup};
+use crate::helper::stateful_list::StatefulList;
+
+/// How conflicting edits are resolved when syncing.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictPolicy {
+    /// Take the value from the server.
+    ServerWins,
+    /// Keep the local value and send it to the server.
+    LocalWins,
+    /// Ask in a merge dialog.
+    #[default]
+    Prompt,
+}
+
+/// Task field that is compared when syncing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Name,
+    Description,
+    Completed,
+    Board,
+    Order,
+}
+
+impl Field {
+    pub const ALL: [Field; 5] = [
+        Field::Name,
+        Field::Description,
+        Field::Completed,
+        Field::Board,
+        Field::Order,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Field::Name => "name",
+            Field::Description => "description",
+            Field::Completed => "completed",
+            Field::Board => "board",
+            Field::Order => "position",
+        }
+    }
+
+    /// The field's value as text, the board is shown as its uuid.
+    pub fn value(&self, task: &Task) -> String {
+        match self {
+            Field::Name => task.name.clone(),
+            Field::Description => task.description.clone(),
+            Field::Completed => task.completed.to_string(),
+            Field::Board => task.board_uuid.clone(),
+            Field::Order => task.order.to_string(),
+        }
+    }
+
+    fn differs(&self, a: &Task, b: &Task) -> bool {
+        self.value(a) != self.value(b)
+    }
+
+    /// Copies the field from `from` into `to`.
+    pub fn copy(&self, from: &Task, to: &mut Task) {
+        match self {
+            Field::Name => to.name = from.name.clone(),
+            Field::Description => to.description = from.description.clone(),
+            Field::Completed => to.completed = from.completed,
+            Field::Board => to.board_uuid = from.board_uuid.clone(),
+            Field::Order => to.order = from.order,
+        }
+    }
+
+    /// The field a patch action changes.
+    pub fn of_action(action: &Action) -> Option<Field> {
+        match action {
+            Action::RenameTask(_) => Some(Field::Name),
+            Action::ChangeDesc(_) => Some(Field::Description),
+            Action::ToggleTask => Some(Field::Completed),
+            Action::MoveBoard(_) => Some(Field::Board),
+            Action::ChangeOrder(_) => Some(Field::Order),
+        }
+    }
+}
+
+/// Which side of a conflict to keep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Local,
+    Server,
+}
+
+/// A field that was changed both locally and on the server since the last sync.
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    pub field: Field,
+    pub local: Task,
+    pub server: Task,
+    pub resolution: Option<Side>,
+}
+
+/// Result of [`merge`].
+#[derive(Debug, Clone)]
+pub struct Merge {
+    /// The merged tasks, conflicting fields hold the local value.
+    pub tasks: Vec<Task>,
+    pub conflicts: Vec<Conflict>,
+}
+
+/// Three way merge of the local tasks with the server's, `base` being the
+/// tasks as they were at the last sync.
+///
+/// Fields changed on one side only are taken from that side, fields changed
+/// on both sides to different values are reported as conflicts.
+pub fn merge(base: &[Task], local: &[Task], server: &[Task]) -> Merge {
+    let find = |tasks: &[Task], uuid: &str| tasks.iter().find(|t| t.uuid == uuid).cloned();
+    let mut tasks = Vec::new();
+    let mut conflicts = Vec::new();
+
+    for server_task in server {
+        let local_task = find(local, &server_task.uuid);
+        let base_task = find(base, &server_task.uuid);
+
+        match (local_task, base_task) {
+            // Deleted locally, the delete is still queued.
+            (None, Some(_)) => {}
+            // Nothing to compare against, the server knows best.
+            (_, None) => tasks.push(server_task.clone()),
+            (Some(local_task), Some(base_task)) => {
+                let mut merged = server_task.clone();
+                for field in Field::ALL {
+                    let local_changed = field.differs(&local_task, &base_task);
+                    let server_changed = field.differs(server_task, &base_task);
+
+                    if local_changed {
+                        field.copy(&local_task, &mut merged);
+                    }
+                    if local_changed && server_changed && field.differs(&local_task, server_task) {
+                        conflicts.push(Conflict {
+                            field,
+                            local: local_task.clone(),
+                            server: server_task.clone(),
+                            resolution: None,
+                        });
+                    }
+                }
+                tasks.push(merged);
+            }
+        }
+    }
+
+    // Created locally and not synced yet, tasks deleted on the server are dropped.
+    for local_task in local {
+        let known = |tasks: &[Task]| tasks.iter().any(|t| t.uuid == local_task.uuid);
+        if !known(server) && !known(base) {
+            tasks.push(local_task.clone());
+        }
+    }
+
+    Merge { tasks, conflicts }
+}
+
+/// Applies the conflict resolutions to the merged tasks.
+///
+/// Returns the uuids of the tasks where the local value won and has to be sent.
+pub fn resolve(tasks: &mut [Task], conflicts: &[Conflict]) -> HashSet<String> {
+    let mut local_wins = HashSet::new();
+
+    for conflict in conflicts {
+        let Some(task) = tasks.iter_mut().find(|t| t.uuid == conflict.local.uuid) else {
+            continue;
+        };
+        match conflict.resolution {
+            Some(Side::Server) => conflict.field.copy(&conflict.server, task),
+            _ => {
+                conflict.field.copy(&conflict.local, task);
+                local_wins.insert(task.uuid.clone());
+            }
+        }
+    }
+    local_wins
+}
+
+/// The patch actions that turn `from` into `to`.
+pub fn patches_between(from: &Task, to: &Task) -> Vec<Action> {
+    let mut actions = Vec::new();
+    if from.name != to.name {
+        actions.push(Action::RenameTask(to.name.clone()));
+    }
+    if from.description != to.description {
+        actions.push(Action::ChangeDesc(to.description.clone()));
+    }
+    if from.completed != to.completed {
+        actions.push(Action::ToggleTask);
+    }
+    if from.board_uuid != to.board_uuid {
+        actions.push(Action::MoveBoard(to.board_uuid.clone()));
+    }
+    if from.order != to.order {
+        actions.push(Action::ChangeOrder(to.order));
+    }
+    actions
+}
+
+/// Replaces the queued patches of the given tasks with the patches that take
+/// the server's version to the merged one.
+///
+/// Queued patches can't be replayed as they are once the server changed the
+/// task, a queued toggle would flip a completion the server already has.
+pub fn rewrite_queue(
+    queue: Vec<Mutation>,
+    server: &[Task],
+    merged: &[Task],
+    mut rewrite: HashSet<String>,
+) -> Vec<Mutation> {
+    for mutation in &queue {
+        if let Mutation::PatchTask { before, .. } = mutation {
+            rewrite.insert(before.uuid.clone());
+        }
+    }
+    let on_server = |uuid: &str| server.iter().any(|t| t.uuid == uuid);
+
+    let mut queue: Vec<Mutation> = queue
+        .into_iter()
+        .filter(|mutation| {
+            !matches!(mutation, Mutation::PatchTask { before, .. } if on_server(&before.uuid))
+        })
+        .collect();
+
+    for server_task in server.iter().filter(|t| rewrite.contains(&t.uuid)) {
+        let Some(target) = merged.iter().find(|t| t.uuid == server_task.uuid) else {
+            continue;
+        };
+        let mut before = server_task.clone();
+        for action in patches_between(server_task, target) {
+            let after = {
+                let mut after = before.clone();
+                action.apply(&mut after);
+                after
+            };
+            queue.push(Mutation::PatchTask { before, action });
+            before = after;
+        }
+    }
+    queue
+}
+
+/// A sync waiting for the user to resolve conflicts.
+#[derive(Debug, Clone)]
+pub struct PendingSync {
+    pub server: Vec<Task>,
+    pub merged: Vec<Task>,
+}
+
+impl App {
+    /// Fetches the user's data in the background, reported as [`Event::Fetched`].
+    pub fn sync(&mut self) {
+        if self.replaying {
+            return;
+        }
+        self.replaying = true;
+        self.last_replay = Some(std::time::Instant::now());
+
+        let client = self.client.clone();
+        let sender = self.event_sender.clone();
+
+        tokio::spawn(async move {
+            let fetched = match client.all_tasks().await {
+                Ok(tasks) => client.all_boards().await.map(|boards| (tasks, boards)),
+                Err(err) => Err(err),
+            };
+            if let Some(sender) = sender {
+                let _ = sender.send(Event::Fetched(fetched));
+            }
+        });
+    }
+
+    /// Merges freshly fetched data into the local state.
+    ///
+    /// Conflicting edits are resolved by the configured [`ConflictPolicy`],
+    /// with [`ConflictPolicy::Prompt`] the sync waits for the merge dialog.
+    pub fn handle_fetched(&mut self, tasks: Vec<Task>, boards: Vec<Board>) {
+        let selected_board = self.boards.state.selected();
+        self.boards = StatefulList::with_items(boards);
+        self.boards.state.select(selected_board);
+        for mutation in self.queue.clone() {
+            mutation.apply_to_boards(&mut self.boards.items);
+        }
+        self.boards.items.sort_by_key(|board| board.order);
+        if self.boards.selected().is_none() {
+            self.boards.unselect();
+            self.boards.next();
+        }
+
+        let Merge {
+            tasks: merged,
+            mut conflicts,
+        } = merge(&self.base_tasks, &self.task_data, &tasks);
+
+        if !conflicts.is_empty() {
+            let side = match self.config.conflict_policy {
+                ConflictPolicy::ServerWins => Side::Server,
+                ConflictPolicy::LocalWins => Side::Local,
+                ConflictPolicy::Prompt => {
+                    self.task_data = merged.clone();
+                    self.popup = Some(Popup::Merge(MergeDialog::new(
+                        conflicts,
+                        PendingSync {
+                            server: tasks,
+                            merged,
+                        },
+                    )));
+                    return;
+                }
+            };
+            conflicts
+                .iter_mut()
+                .for_each(|conflict| conflict.resolution = Some(side));
+        }
+
+        self.finish_sync(
+            PendingSync {
+                server: tasks,
+                merged,
+            },
+            &conflicts,
+        );
+    }
+
+    /// Takes over the merged tasks and sends what the server is missing.
+    pub fn finish_sync(&mut self, pending: PendingSync, conflicts: &[Conflict]) {
+        let PendingSync { server, mut merged } = pending;
+        let local_wins = resolve(&mut merged, conflicts);
+
+        let queue = std::mem::take(&mut self.queue);
+        self.queue = rewrite_queue(queue, &server, &merged, local_wins);
+        self.task_data = merged;
+        self.base_tasks = server;
+        self.save_cache();
+
+        if self.queue.is_empty() {
+            if self.offline {
+                self.offline = false;
+                self.notify("Back online");
+            }
+        } else {
+            self.replay_queue();
+        }
+    }
+}
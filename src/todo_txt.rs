@@ -0,0 +1,187 @@
+use std::fmt::Write;
+
+use crate::api::board::{Board, BoardKind};
+use crate::api::task::Task;
+
+/// Tag holding the task description, percent encoded as tag values can't
+/// contain spaces.
+pub const DESCRIPTION_TAG: &str = "desc";
+
+/// Marks a name word that would otherwise be read as todo.txt syntax.
+const ESCAPE: char = '\\';
+
+/// A task read from a todo.txt line.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TodoItem {
+    /// The text without the project and description, `@contexts`, the
+    /// priority and other tags stay in it.
+    pub name: String,
+    pub description: String,
+    pub completed: bool,
+    /// The first `+project`, the board of the task.
+    pub project: Option<String>,
+}
+
+/// Parses a todo.txt line, `None` for blank lines and lines without text.
+///
+/// Dates are dropped since tasks have none. A leading `\` is removed from
+/// the words of the name, see [`format_task`].
+pub fn parse_line(line: &str) -> Option<TodoItem> {
+    let mut item = TodoItem::default();
+    let mut tokens: Vec<&str> = line.split_whitespace().collect();
+
+    if tokens.first() == Some(&"x") {
+        item.completed = true;
+        tokens.remove(0);
+        // completion and creation date
+        for _ in 0..2 {
+            if tokens.first().is_some_and(|token| is_date(token)) {
+                tokens.remove(0);
+            }
+        }
+    } else {
+        let start = usize::from(tokens.first().is_some_and(|token| is_priority(token)));
+        if tokens.get(start).is_some_and(|token| is_date(token)) {
+            tokens.remove(start);
+        }
+    }
+
+    let mut words = Vec::new();
+    for token in tokens {
+        if let Some(project) = token.strip_prefix('+').filter(|p| !p.is_empty()) {
+            if item.project.is_none() {
+                item.project = Some(project.to_string());
+                continue;
+            }
+        }
+        if let Some(value) = token
+            .strip_prefix(DESCRIPTION_TAG)
+            .and_then(|rest| rest.strip_prefix(':'))
+        {
+            item.description = decode(value);
+            continue;
+        }
+        words.push(token.strip_prefix(ESCAPE).unwrap_or(token));
+    }
+
+    item.name = words.join(" ");
+    (!item.name.is_empty()).then_some(item)
+}
+
+/// The task as a todo.txt line, its board as `+project` unless it's the inbox.
+///
+/// Name words [`parse_line`] would take for the completion mark, a date, the
+/// project or the description get a leading `\`, so the line reads back as
+/// the same task.
+pub fn format_task(task: &Task, board: Option<&Board>) -> String {
+    let mut line = String::new();
+    if task.completed {
+        line.push_str("x ");
+    }
+    let words: Vec<String> = task
+        .name
+        .split_whitespace()
+        .enumerate()
+        .map(|(index, word)| match needs_escape(index, word) {
+            true => format!("{}{}", ESCAPE, word),
+            false => word.to_string(),
+        })
+        .collect();
+    line.push_str(&words.join(" "));
+    if let Some(board) = board.filter(|board| board.kind != BoardKind::Inbox) {
+        write!(line, " +{}", project_name(&board.name)).unwrap();
+    }
+    if !task.description.is_empty() {
+        write!(line, " {}:{}", DESCRIPTION_TAG, encode(&task.description)).unwrap();
+    }
+    line
+}
+
+/// The tasks of the boards, one line each in board and task order.
+pub fn export(boards: &[&Board], tasks: &[Task]) -> String {
+    let mut output = String::new();
+    for board in boards {
+        let mut board_tasks: Vec<&Task> = tasks
+            .iter()
+            .filter(|task| task.board_uuid == board.uuid)
+            .collect();
+        board_tasks.sort_by_key(|task| task.order);
+
+        for task in board_tasks {
+            writeln!(output, "{}", format_task(task, Some(board))).unwrap();
+        }
+    }
+    output
+}
+
+/// The board name as a project, projects end at whitespace.
+pub fn project_name(board_name: &str) -> String {
+    board_name.split_whitespace().collect::<Vec<_>>().join("-")
+}
+
+/// Whether the name word at the index would be misread, dates are dropped
+/// when among the first two words, after the completion mark or a priority.
+fn needs_escape(index: usize, word: &str) -> bool {
+    word.starts_with(ESCAPE)
+        || (index == 0 && word == "x")
+        || (index < 2 && is_date(word))
+        || word
+            .strip_prefix('+')
+            .is_some_and(|project| !project.is_empty())
+        || word
+            .strip_prefix(DESCRIPTION_TAG)
+            .is_some_and(|rest| rest.starts_with(':'))
+}
+
+fn is_date(token: &str) -> bool {
+    let bytes = token.as_bytes();
+    bytes.len() == 10
+        && bytes.iter().enumerate().all(|(index, byte)| match index {
+            4 | 7 => *byte == b'-',
+            _ => byte.is_ascii_digit(),
+        })
+}
+
+fn is_priority(token: &str) -> bool {
+    let bytes = token.as_bytes();
+    bytes.len() == 3 && bytes[0] == b'(' && bytes[1].is_ascii_uppercase() && bytes[2] == b')'
+}
+
+/// Percent encodes `%` and whitespace.
+fn encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for c in text.chars() {
+        if c == '%' || c.is_whitespace() {
+            for byte in c.to_string().bytes() {
+                write!(encoded, "%{:02X}", byte).unwrap();
+            }
+        } else {
+            encoded.push(c);
+        }
+    }
+    encoded
+}
+
+fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
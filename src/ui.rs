@@ -2,11 +2,17 @@ use tui::{
     backend::Backend,
     prelude::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, BorderType, Borders, List, ListItem, Paragraph},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, Paragraph},
     Frame,
 };
 
-use crate::{app::App, helper::input::InputContentVariants};
+use crate::{
+    api::board::{BoardKind, Boards},
+    app::App,
+    helper::popup::Popup,
+    sync::{Field, Side},
+};
 
 /// Renders the user interface widgets.
 pub fn render<B: Backend>(app: &mut App, frame: &mut Frame<'_, B>) {
@@ -20,7 +26,32 @@ pub fn render<B: Backend>(app: &mut App, frame: &mut Frame<'_, B>) {
         None => String::from("Guest"),
     };
 
-    let navbar = Paragraph::new(username).block(
+    let mut navbar_line = vec![Span::raw(username)];
+    if let Some(profile) = &app.profile {
+        navbar_line.push(Span::styled(
+            format!(" [{}]", profile),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    if app.offline {
+        navbar_line.push(Span::styled(
+            format!(" (offline, {} queued)", app.queue.len()),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if let Some(notification) = &app.notification {
+        navbar_line.push(Span::raw("  "));
+        navbar_line.push(Span::styled(
+            notification.message.clone(),
+            Style::default().fg(if notification.is_error {
+                Color::Red
+            } else {
+                Color::Green
+            }),
+        ));
+    }
+
+    let navbar = Paragraph::new(Line::from(navbar_line)).block(
         Block::default()
             .title("QuickTick")
             .borders(Borders::ALL)
@@ -29,30 +60,26 @@ pub fn render<B: Backend>(app: &mut App, frame: &mut Frame<'_, B>) {
     // Adjust the style as needed
 
     // TASKS
-    let tasks_to_display = app
+    let selected_board = app.boards.selected().cloned();
+    let archive_uuid = app
+        .boards
+        .items
+        .of_kind(BoardKind::Archive)
+        .map(|board| board.uuid.clone());
+
+    let mut tasks_to_display = app
         .task_data
         .iter()
         .filter(|task| {
-            let archive_uuid = app
-                .boards
-                .items
-                .iter()
-                .find(|board| board.special == Some(3))
-                .unwrap_or(&app.boards.items[0])
-                .uuid
-                .clone();
-
-            app.boards
-                .selected()
-                .is_some_and(|board| board.special.is_some_and(|special| special == 1))
-                && (task.board_uuid == archive_uuid)
-                || app
-                    .boards
-                    .selected()
-                    .is_some_and(|board| board.uuid == task.board_uuid)
+            selected_board.as_ref().is_some_and(|board| {
+                board.uuid == task.board_uuid
+                    || board.kind == BoardKind::Inbox
+                        && archive_uuid.as_ref() == Some(&task.board_uuid)
+            })
         })
         .cloned()
         .collect::<Vec<_>>();
+    tasks_to_display.sort_by_key(|task| task.order);
 
     app.tasks.items = tasks_to_display;
     let items = app
@@ -140,61 +167,160 @@ pub fn render<B: Backend>(app: &mut App, frame: &mut Frame<'_, B>) {
 
     let screen_size = frame.size();
 
-    let mut input_content = app.input_content.clone();
-
-    let input_fields = input_content.ui_to_render();
+    let input_fields = app.input_content.ui_to_render();
+    let field_heights = app.input_content.field_heights();
 
     let layout_input = Layout::default()
         .direction(Direction::Vertical)
         .constraints(
-            input_fields
+            field_heights
                 .iter()
-                .map(|_| Constraint::Length(3))
-                .collect::<Vec<_>>()
-                .as_ref(),
+                .map(|height| Constraint::Length(*height))
+                .collect::<Vec<_>>(),
         )
         .split(Rect::new(
             screen_size.width / 2 - 20,
             screen_size.height / 2 - 5,
             50,
-            match &app.input_content.variant {
-                InputContentVariants::CreateTask { .. } => 9,
-                InputContentVariants::CreateBoard { .. } => 3,
-                InputContentVariants::UpdateTask { .. } => 9,
-                InputContentVariants::LogIn { .. } => 6,
-                InputContentVariants::SignUp { .. } => 9,
-                InputContentVariants::ChangeUsername { .. } => 3,
-            },
+            field_heights.iter().sum(),
         ));
 
     if app.input_content.visible {
-        for (index, input) in input_fields.iter().enumerate() {
-            frame.render_widget(input.clone(), layout_input[index]);
+        for (index, input) in input_fields.into_iter().enumerate() {
+            frame.render_widget(input, layout_input[index]);
         }
+
+        let (x, y) = app.input_content.cursor_coordinates();
+        let field = layout_input[app.input_content.selected_input];
+        frame.set_cursor(field.x + x, field.y + y);
     }
 
-    match app.input_content.visible {
-        true => {
-            frame.set_cursor(
-                match (
-                    &app.input_content.variant,
-                    &app.input_content.selected_input,
-                ) {
-                    (InputContentVariants::CreateTask { .. }, 1) => {
-                        layout_input[0].x + (app.input_content.selected_input_len() as u16 + 1) % 48
-                            // for each 48 characters, add 1 
-                            + app.input_content.selected_input_len() as u16 / 48
-                    }
-                    (InputContentVariants::UpdateTask { .. }, 1) => {
-                        layout_input[0].x + (app.input_content.selected_input_len() as u16 + 1) % 48
-                    }
-                    _ => layout_input[0].x + (app.input_content.selected_input_len() as u16 + 1),
-                },
-                // layout_input[0].x + (app.input_content.selected_input_len() as u16 + 1) % 50,
-                layout_input[app.input_content.selected_input].y
-                    + app.input_content.cursor_coordinates().1,
-            );
+    render_popup(app, frame);
+}
+
+/// Returns a `width` x `height` rect in the middle of `area`.
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// Renders the open [`Popup`], if any.
+fn render_popup<B: Backend>(app: &mut App, frame: &mut Frame<'_, B>) {
+    let board_name = |uuid: &str| {
+        app.boards
+            .items
+            .iter()
+            .find(|board| board.uuid == uuid)
+            .map_or(uuid.to_string(), |board| board.name.clone())
+    };
+
+    match &mut app.popup {
+        Some(Popup::Merge(dialog)) => {
+            let items: Vec<_> = dialog
+                .conflicts
+                .items
+                .iter()
+                .map(|conflict| {
+                    let value = |task| match conflict.field {
+                        Field::Board => board_name(&conflict.field.value(task)),
+                        field => field.value(task),
+                    };
+                    let marker = match conflict.resolution {
+                        Some(Side::Local) => "[local] ",
+                        Some(Side::Server) => "[server]",
+                        None => "[  ?   ]",
+                    };
+                    ListItem::new(format!(
+                        "{} {} / {}: local \"{}\", server \"{}\"",
+                        marker,
+                        conflict.local.name,
+                        conflict.field.label(),
+                        value(&conflict.local),
+                        value(&conflict.server),
+                    ))
+                })
+                .collect();
+
+            let area = centered_rect(80, items.len() as u16 + 2, frame.size());
+            let list = List::new(items)
+                .block(
+                    Block::default()
+                        .title("Conflicts: l/s keep local/server, L/S all, Enter apply, Esc later")
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Double),
+                )
+                .style(Style::default().bg(Color::Indexed(235)).fg(Color::White))
+                .highlight_style(Style::default().add_modifier(Modifier::ITALIC))
+                .highlight_symbol(" ");
+
+            frame.render_widget(Clear, area);
+            frame.render_stateful_widget(list, area, &mut dialog.conflicts.state);
+        }
+        Some(Popup::BoardPicker { boards, .. }) => {
+            let items: Vec<_> = boards
+                .items
+                .iter()
+                .map(|board| ListItem::new(board.name.clone()))
+                .collect();
+
+            let area = centered_rect(40, items.len() as u16 + 2, frame.size());
+            let list = List::new(items)
+                .block(
+                    Block::default()
+                        .title("Move to board")
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Double),
+                )
+                .style(Style::default().bg(Color::Indexed(235)).fg(Color::White))
+                .highlight_style(Style::default().add_modifier(Modifier::ITALIC))
+                .highlight_symbol(" ");
+
+            frame.render_widget(Clear, area);
+            frame.render_stateful_widget(list, area, &mut boards.state);
+        }
+        Some(Popup::ProfilePicker(profiles)) => {
+            let items: Vec<_> = profiles
+                .items
+                .iter()
+                .map(|profile| ListItem::new(profile.as_deref().unwrap_or("default").to_string()))
+                .collect();
+
+            let area = centered_rect(40, items.len() as u16 + 2, frame.size());
+            let list = List::new(items)
+                .block(
+                    Block::default()
+                        .title("Switch profile")
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Double),
+                )
+                .style(Style::default().bg(Color::Indexed(235)).fg(Color::White))
+                .highlight_style(Style::default().add_modifier(Modifier::ITALIC))
+                .highlight_symbol(" ");
+
+            frame.render_widget(Clear, area);
+            frame.render_stateful_widget(list, area, &mut profiles.state);
+        }
+        Some(Popup::Confirm(dialog)) => {
+            let width = (dialog.message.chars().count() as u16 + 4).max(30);
+            let area = centered_rect(width, 3, frame.size());
+            let paragraph = Paragraph::new(dialog.message.clone())
+                .block(
+                    Block::default()
+                        .title("y confirm, n/Esc cancel")
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Double),
+                )
+                .style(Style::default().bg(Color::Indexed(235)).fg(Color::White));
+
+            frame.render_widget(Clear, area);
+            frame.render_widget(paragraph, area);
         }
-        false => {}
+        None => {}
     }
 }
@@ -0,0 +1,117 @@
+use quicktick_tty::cli::output::{Format, Output};
+use quicktick_tty::cli::{Args, Command};
+
+fn parse(args: &[&str]) -> Result<Args, String> {
+    Args::parse(args.iter().map(|arg| arg.to_string()))
+}
+
+fn command(args: &[&str]) -> Command {
+    parse(args).unwrap().command.unwrap()
+}
+
+#[test]
+fn no_arguments_start_the_interface() {
+    let args = parse(&[]).unwrap();
+
+    assert!(args.command.is_none());
+    assert!(!args.help);
+}
+
+#[test]
+fn add_takes_its_flags_anywhere() {
+    assert_eq!(
+        command(&[
+            "add",
+            "--board",
+            "Work",
+            "Buy milk",
+            "--description=2 litres"
+        ]),
+        Command::Add {
+            name: "Buy milk".to_string(),
+            board: Some("Work".to_string()),
+            description: Some("2 litres".to_string()),
+        }
+    );
+}
+
+#[test]
+fn arguments_after_double_dash_are_positional() {
+    assert_eq!(
+        command(&["add", "--board", "Work", "--", "-1 fix bug"]),
+        Command::Add {
+            name: "-1 fix bug".to_string(),
+            board: Some("Work".to_string()),
+            description: None,
+        }
+    );
+    assert_eq!(
+        command(&["--", "done", "--help"]),
+        Command::Done {
+            task: "--help".to_string()
+        }
+    );
+}
+
+#[test]
+fn dashes_without_double_dash_are_flags() {
+    assert_eq!(
+        parse(&["add", "-1 fix bug"]).unwrap_err(),
+        "unknown argument: -1 fix bug"
+    );
+}
+
+#[test]
+fn global_options_are_kept() {
+    let args = parse(&[
+        "--server",
+        "http://localhost:8080",
+        "--profile=work",
+        "boards",
+    ])
+    .unwrap();
+
+    assert_eq!(args.server.as_deref(), Some("http://localhost:8080"));
+    assert_eq!(args.profile.as_deref(), Some("work"));
+    assert_eq!(
+        args.command,
+        Some(Command::Boards {
+            output: Output::default()
+        })
+    );
+}
+
+#[test]
+fn output_options_apply_to_list() {
+    assert_eq!(
+        command(&["list", "--json", "--fields", "name,board"]),
+        Command::List {
+            board: None,
+            output: Output {
+                format: Format::Json,
+                fields: Some(vec!["name".to_string(), "board".to_string()]),
+            },
+        }
+    );
+}
+
+#[test]
+fn misplaced_arguments_are_errors() {
+    assert_eq!(parse(&["done"]).unwrap_err(), "done expects a task uuid");
+    assert_eq!(
+        parse(&["done", "a8f8", "b9"]).unwrap_err(),
+        "unexpected argument: b9"
+    );
+    assert_eq!(
+        parse(&["done", "a8f8", "--json"]).unwrap_err(),
+        "done takes no --json, --format or --fields"
+    );
+    assert_eq!(
+        parse(&["add", "--board"]).unwrap_err(),
+        "--board expects a board name"
+    );
+    assert_eq!(
+        parse(&["frobnicate"]).unwrap_err(),
+        "unknown command: frobnicate"
+    );
+}
@@ -0,0 +1,41 @@
+use quicktick_tty::api::client::login_path_url;
+use quicktick_tty::api::user::Credentials;
+
+fn credentials(email: &str, password: &str) -> Credentials {
+    Credentials {
+        email: email.to_string(),
+        password: password.to_string(),
+    }
+}
+
+#[test]
+fn login_path_keeps_each_credential_in_one_segment() {
+    let url = login_path_url(
+        "https://quicktick.example.com",
+        &credentials("me@example.com", "a/b?c#d"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        url.as_str(),
+        "https://quicktick.example.com/login/me@example.com/a%2Fb%3Fc%23d?device_identifier=tty"
+    );
+    assert_eq!(url.path_segments().unwrap().count(), 3);
+    assert_eq!(url.fragment(), None);
+}
+
+#[test]
+fn login_path_extends_the_base_path() {
+    let url = login_path_url(
+        "https://example.com/quicktick/",
+        &credentials("me/you", "100% sure"),
+    )
+    .unwrap();
+
+    assert_eq!(url.path(), "/quicktick/login/me%2Fyou/100%25%20sure");
+}
+
+#[test]
+fn login_path_rejects_invalid_base_urls() {
+    assert!(login_path_url("not a url", &credentials("me", "pw")).is_err());
+}
@@ -0,0 +1,136 @@
+use quicktick_tty::api::board::{Board, BoardAction, BoardKind};
+use quicktick_tty::api::request::{CreateUser, Login};
+use quicktick_tty::api::task::{Action, Task};
+use serde_json::{json, Value};
+
+/// Names that broke the hand written json bodies.
+const TRICKY_NAMES: [&str; 6] = [
+    "Say \"hi\"",
+    "back\\slash",
+    "two\nlines",
+    "tab\there",
+    "🥛 Buy milk 🐄",
+    "\", \"action\": \"DeleteTask",
+];
+
+const TASK_UUID: &str = "9b2f4c1e-0000-4000-8000-000000000001";
+const BOARD_UUID: &str = "9b2f4c1e-0000-4000-8000-000000000002";
+
+/// Serializes the body to a string like the client does and parses it back.
+fn roundtrip(body: &impl serde::Serialize) -> Value {
+    let text = serde_json::to_string(body).expect("body serializes");
+    serde_json::from_str(&text).expect("body is valid json")
+}
+
+#[test]
+fn rename_task_keeps_the_name_intact() {
+    for name in TRICKY_NAMES {
+        let action = Action::RenameTask(name.to_string());
+        assert_eq!(
+            roundtrip(&action.request(TASK_UUID)),
+            json!({ "task_uuid": TASK_UUID, "action": "RenameTask", "name": name }),
+        );
+    }
+}
+
+#[test]
+fn change_desc_keeps_the_description_intact() {
+    for description in TRICKY_NAMES {
+        let action = Action::ChangeDesc(description.to_string());
+        assert_eq!(
+            roundtrip(&action.request(TASK_UUID)),
+            json!({
+                "task_uuid": TASK_UUID,
+                "action": "ChangeDesc",
+                "description": description,
+            }),
+        );
+    }
+}
+
+#[test]
+fn task_actions_without_text() {
+    assert_eq!(
+        roundtrip(&Action::ToggleTask.request(TASK_UUID)),
+        json!({ "task_uuid": TASK_UUID, "action": "ToggleTask" }),
+    );
+    assert_eq!(
+        roundtrip(&Action::ChangeOrder(-3).request(TASK_UUID)),
+        json!({ "task_uuid": TASK_UUID, "action": "ChangeOrder", "order": -3 }),
+    );
+    assert_eq!(
+        roundtrip(&Action::MoveBoard(BOARD_UUID.to_string()).request(TASK_UUID)),
+        json!({ "task_uuid": TASK_UUID, "action": "MoveBoard", "board_uuid": BOARD_UUID }),
+    );
+}
+
+#[test]
+fn board_actions() {
+    for name in TRICKY_NAMES {
+        let action = BoardAction::RenameBoard(name.to_string());
+        assert_eq!(
+            roundtrip(&action.request(BOARD_UUID)),
+            json!({ "board_uuid": BOARD_UUID, "action": "RenameBoard", "name": name }),
+        );
+    }
+    assert_eq!(
+        roundtrip(&BoardAction::ChangeOrder(4).request(BOARD_UUID)),
+        json!({ "board_uuid": BOARD_UUID, "action": "ChangeOrder", "order": 4 }),
+    );
+}
+
+#[test]
+fn create_user_keeps_every_field_intact() {
+    for text in TRICKY_NAMES {
+        let body = CreateUser {
+            email: "milk@example.com",
+            password: text,
+            name: text,
+        };
+        assert_eq!(
+            roundtrip(&body),
+            json!({ "email": "milk@example.com", "password": text, "name": text }),
+        );
+    }
+}
+
+#[test]
+fn login_keeps_the_password_intact() {
+    for password in TRICKY_NAMES {
+        let body = Login {
+            email: "milk@example.com",
+            password,
+        };
+        assert_eq!(
+            roundtrip(&body),
+            json!({ "email": "milk@example.com", "password": password }),
+        );
+    }
+}
+
+#[test]
+fn created_tasks_and_boards_keep_their_names() {
+    for name in TRICKY_NAMES {
+        let task = Task {
+            uuid: TASK_UUID.to_string(),
+            name: name.to_string(),
+            description: name.to_string(),
+            completed: false,
+            user_uuid: "user".to_string(),
+            board_uuid: BOARD_UUID.to_string(),
+            order: 0,
+        };
+        let body = roundtrip(&task);
+        assert_eq!(body["name"], name);
+        assert_eq!(body["description"], name);
+
+        let board = Board {
+            uuid: BOARD_UUID.to_string(),
+            name: name.to_string(),
+            user_uuid: "user".to_string(),
+            kind: BoardKind::Normal,
+            order: 0,
+        };
+        assert_eq!(roundtrip(&board)["name"], name);
+    }
+}
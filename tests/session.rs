@@ -0,0 +1,82 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Once;
+
+use quicktick_tty::session::{Session, SessionError};
+
+const COOKIES: &str = "session=abc; user_uuid=9b2f4c1e";
+
+/// Points the config dir at a fresh temporary directory, once per test run.
+///
+/// Every test uses its own profile, so they don't share files.
+fn config_home() -> PathBuf {
+    static INIT: Once = Once::new();
+    let home = std::env::temp_dir().join(format!("quicktick-tty-session-{}", std::process::id()));
+    INIT.call_once(|| {
+        let _ = fs::remove_dir_all(&home);
+        fs::create_dir_all(&home).unwrap();
+        std::env::set_var("XDG_CONFIG_HOME", &home);
+    });
+    home
+}
+
+fn session() -> Session {
+    Session {
+        cookies: COOKIES.to_string(),
+    }
+}
+
+#[test]
+fn encrypted_sessions_round_trip() {
+    config_home();
+    let profile = Some("round-trip");
+
+    session().save(profile, Some("hunter2")).unwrap();
+
+    let stored = fs::read_to_string(Session::path(profile)).unwrap();
+    assert!(!stored.contains(COOKIES), "cookies stored in the clear");
+    let loaded = Session::load(profile, Some("hunter2")).unwrap().unwrap();
+    assert_eq!(loaded.cookies, COOKIES);
+}
+
+#[test]
+fn a_wrong_passphrase_is_an_error() {
+    config_home();
+    let profile = Some("wrong-passphrase");
+
+    session().save(profile, Some("hunter2")).unwrap();
+
+    assert!(matches!(
+        Session::load(profile, Some("hunter3")),
+        Err(SessionError::WrongPassphrase)
+    ));
+    assert!(matches!(
+        Session::load(profile, None),
+        Err(SessionError::PassphraseRequired)
+    ));
+}
+
+#[test]
+fn plaintext_sessions_still_load() {
+    config_home();
+    let profile = Some("plaintext");
+    fs::create_dir_all(Session::path(profile).parent().unwrap()).unwrap();
+    fs::write(
+        Session::path(profile),
+        format!(r#"{{"kind":"plain","cookies":"{}"}}"#, COOKIES),
+    )
+    .unwrap();
+
+    // Also once encryption was turned on and a passphrase is given.
+    for passphrase in [None, Some("hunter2")] {
+        let loaded = Session::load(profile, passphrase).unwrap().unwrap();
+        assert_eq!(loaded.cookies, COOKIES);
+    }
+}
+
+#[test]
+fn missing_sessions_are_none() {
+    config_home();
+
+    assert!(Session::load(Some("missing"), None).unwrap().is_none());
+}
@@ -0,0 +1,211 @@
+use std::collections::HashSet;
+use std::slice;
+
+use quicktick_tty::api::mutation::Mutation;
+use quicktick_tty::api::task::{Action, Task};
+use quicktick_tty::sync::{merge, resolve, rewrite_queue, Field, Side};
+
+const BOARD_UUID: &str = "9b2f4c1e-0000-4000-8000-000000000002";
+
+fn task(uuid: &str, name: &str) -> Task {
+    Task {
+        uuid: uuid.to_string(),
+        name: name.to_string(),
+        description: String::new(),
+        completed: false,
+        user_uuid: "user".to_string(),
+        board_uuid: BOARD_UUID.to_string(),
+        order: 0,
+    }
+}
+
+fn renamed(task: &Task, name: &str) -> Task {
+    Task {
+        name: name.to_string(),
+        ..task.clone()
+    }
+}
+
+fn toggled(task: &Task) -> Task {
+    Task {
+        completed: !task.completed,
+        ..task.clone()
+    }
+}
+
+#[test]
+fn edits_on_one_side_are_taken_without_conflict() {
+    let base = task("a", "Milk");
+    let local = renamed(&base, "Buy milk");
+    let server = toggled(&base);
+
+    let merge = merge(slice::from_ref(&base), &[local], &[server]);
+
+    assert!(merge.conflicts.is_empty());
+    assert_eq!(merge.tasks.len(), 1);
+    assert_eq!(merge.tasks[0].name, "Buy milk");
+    assert!(merge.tasks[0].completed);
+}
+
+#[test]
+fn the_same_edit_on_both_sides_is_no_conflict() {
+    let base = task("a", "Milk");
+    let local = renamed(&base, "Buy milk");
+    let server = renamed(&base, "Buy milk");
+
+    let merge = merge(&[base], &[local], &[server]);
+
+    assert!(merge.conflicts.is_empty());
+    assert_eq!(merge.tasks[0].name, "Buy milk");
+}
+
+#[test]
+fn different_edits_on_both_sides_conflict() {
+    let base = task("a", "Milk");
+    let local = renamed(&base, "Buy milk");
+    let server = renamed(&base, "Buy oat milk");
+
+    let merge = merge(&[base], &[local], &[server]);
+
+    assert_eq!(merge.conflicts.len(), 1);
+    assert_eq!(merge.conflicts[0].field, Field::Name);
+    assert_eq!(merge.conflicts[0].resolution, None);
+    // Until resolved the local value is kept.
+    assert_eq!(merge.tasks[0].name, "Buy milk");
+}
+
+#[test]
+fn resolving_conflicts_picks_the_side() {
+    let base = task("a", "Milk");
+    let local = renamed(&base, "Buy milk");
+    let server = renamed(&base, "Buy oat milk");
+    let merged = merge(&[base], &[local], &[server]);
+
+    let mut conflicts = merged.conflicts.clone();
+    conflicts[0].resolution = Some(Side::Server);
+    let mut tasks = merged.tasks.clone();
+    assert!(resolve(&mut tasks, &conflicts).is_empty());
+    assert_eq!(tasks[0].name, "Buy oat milk");
+
+    conflicts[0].resolution = Some(Side::Local);
+    let mut tasks = merged.tasks.clone();
+    assert_eq!(
+        resolve(&mut tasks, &conflicts),
+        HashSet::from(["a".to_string()])
+    );
+    assert_eq!(tasks[0].name, "Buy milk");
+}
+
+#[test]
+fn local_deletes_stay_deleted() {
+    let base = task("a", "Milk");
+    // Deleted here, the delete is still in the queue.
+    let merge = merge(slice::from_ref(&base), &[], slice::from_ref(&base));
+
+    assert!(merge.tasks.is_empty());
+    assert!(merge.conflicts.is_empty());
+}
+
+#[test]
+fn server_deletes_are_taken_and_local_creations_kept() {
+    let base = task("a", "Milk");
+    let created = task("b", "Bread");
+
+    let merge = merge(
+        slice::from_ref(&base),
+        &[base.clone(), created.clone()],
+        &[],
+    );
+
+    assert_eq!(merge.tasks, vec![created]);
+}
+
+#[test]
+fn tasks_created_on_the_server_are_taken() {
+    let server = task("a", "Milk");
+
+    let merge = merge(&[], &[], slice::from_ref(&server));
+
+    assert_eq!(merge.tasks, vec![server]);
+}
+
+#[test]
+fn queued_toggle_is_not_replayed_when_the_server_has_it() {
+    let base = task("a", "Milk");
+    let local = toggled(&base);
+    let server = toggled(&base);
+    let queue = vec![Mutation::PatchTask {
+        before: base.clone(),
+        action: Action::ToggleTask,
+    }];
+
+    let merge = merge(&[base], &[local], slice::from_ref(&server));
+    let queue = rewrite_queue(queue, &[server], &merge.tasks, HashSet::new());
+
+    assert!(merge.tasks[0].completed);
+    // Replaying the toggle would have marked the task as not done again.
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn queued_patches_are_rebased_on_the_server_task() {
+    let base = task("a", "Milk");
+    let local = toggled(&renamed(&base, "Buy milk"));
+    let server = toggled(&base);
+    let created = task("b", "Bread");
+    let queue = vec![
+        Mutation::PatchTask {
+            before: base.clone(),
+            action: Action::RenameTask("Buy milk".to_string()),
+        },
+        Mutation::PatchTask {
+            before: renamed(&base, "Buy milk"),
+            action: Action::ToggleTask,
+        },
+        Mutation::CreateTask(created.clone()),
+    ];
+
+    let merge = merge(&[base], &[local, created.clone()], slice::from_ref(&server));
+    let queue = rewrite_queue(
+        queue,
+        slice::from_ref(&server),
+        &merge.tasks,
+        HashSet::new(),
+    );
+
+    assert_eq!(
+        queue,
+        vec![
+            Mutation::CreateTask(created),
+            Mutation::PatchTask {
+                before: server,
+                action: Action::RenameTask("Buy milk".to_string()),
+            },
+        ]
+    );
+}
+
+#[test]
+fn local_wins_are_sent_as_patches() {
+    let base = task("a", "Milk");
+    let local = renamed(&base, "Buy milk");
+    let server = renamed(&base, "Buy oat milk");
+    let mut merged = merge(&[base], &[local], slice::from_ref(&server));
+    merged.conflicts[0].resolution = Some(Side::Local);
+
+    let local_wins = resolve(&mut merged.tasks, &merged.conflicts);
+    let queue = rewrite_queue(
+        Vec::new(),
+        slice::from_ref(&server),
+        &merged.tasks,
+        local_wins,
+    );
+
+    assert_eq!(
+        queue,
+        vec![Mutation::PatchTask {
+            before: server,
+            action: Action::RenameTask("Buy milk".to_string()),
+        }]
+    );
+}
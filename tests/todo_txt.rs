@@ -0,0 +1,131 @@
+use quicktick_tty::api::board::{Board, BoardKind};
+use quicktick_tty::api::task::Task;
+use quicktick_tty::todo_txt::{format_task, parse_line, TodoItem};
+
+fn board(name: &str, kind: BoardKind) -> Board {
+    Board {
+        uuid: "9b2f4c1e-0000-4000-8000-000000000002".to_string(),
+        name: name.to_string(),
+        user_uuid: "user".to_string(),
+        kind,
+        order: 0,
+    }
+}
+
+fn task(name: &str, description: &str, completed: bool) -> Task {
+    Task {
+        uuid: "a".to_string(),
+        name: name.to_string(),
+        description: description.to_string(),
+        completed,
+        user_uuid: "user".to_string(),
+        board_uuid: "9b2f4c1e-0000-4000-8000-000000000002".to_string(),
+        order: 0,
+    }
+}
+
+/// Exports the task and reads the line back.
+fn round_trip(task: &Task, board: &Board) -> TodoItem {
+    let line = format_task(task, Some(board));
+    parse_line(&line).unwrap_or_else(|| panic!("{:?} didn't parse", line))
+}
+
+#[test]
+fn parses_completion_project_and_description() {
+    let item = parse_line("x 2024-05-02 2024-05-01 Ship it +Work desc:Before%20Friday").unwrap();
+
+    assert_eq!(
+        item,
+        TodoItem {
+            name: "Ship it".to_string(),
+            description: "Before Friday".to_string(),
+            completed: true,
+            project: Some("Work".to_string()),
+        }
+    );
+}
+
+#[test]
+fn keeps_priority_and_contexts_in_the_name() {
+    let item = parse_line("(A) 2024-05-01 Call mom @phone +Family +Later").unwrap();
+
+    assert_eq!(item.name, "(A) Call mom @phone +Later");
+    assert_eq!(item.project.as_deref(), Some("Family"));
+    assert!(!item.completed);
+}
+
+#[test]
+fn blank_lines_are_skipped() {
+    assert_eq!(parse_line(""), None);
+    assert_eq!(parse_line("   "), None);
+    assert_eq!(parse_line("x 2024-05-01"), None);
+}
+
+#[test]
+fn plain_tasks_round_trip() {
+    let work = board("Work", BoardKind::Normal);
+    let task = task("Ship it @office", "Line one\nLine two 100%", true);
+
+    let line = format_task(&task, Some(&work));
+    assert_eq!(
+        line,
+        "x Ship it @office +Work desc:Line%20one%0ALine%20two%20100%25"
+    );
+
+    let item = parse_line(&line).unwrap();
+    assert_eq!(item.name, task.name);
+    assert_eq!(item.description, task.description);
+    assert!(item.completed);
+    assert_eq!(item.project.as_deref(), Some("Work"));
+}
+
+#[test]
+fn inbox_tasks_have_no_project() {
+    let inbox = board("Inbox", BoardKind::Inbox);
+
+    let item = round_trip(&task("Milk", "", false), &inbox);
+
+    assert_eq!(item.project, None);
+}
+
+#[test]
+fn names_that_look_like_syntax_round_trip() {
+    let work = board("Work", BoardKind::Normal);
+    let inbox = board("Inbox", BoardKind::Inbox);
+    let names = [
+        "x marks the spot",
+        "2024-05-01 deadline",
+        "(A) 2024-05-01 call",
+        "Read about +projects",
+        "Fix desc:tags",
+        r"C:\ and \\server paths",
+        "(B) stays first",
+    ];
+
+    for name in names {
+        for completed in [false, true] {
+            for board in [&work, &inbox] {
+                let item = round_trip(&task(name, "", completed), board);
+                assert_eq!(item.name, name);
+                assert_eq!(item.completed, completed, "{}", name);
+                assert_eq!(item.description, "");
+            }
+        }
+    }
+
+    let item = round_trip(&task("x", "", false), &inbox);
+    assert_eq!(item.name, "x");
+    assert!(!item.completed);
+}
+
+#[test]
+fn only_ambiguous_words_are_escaped() {
+    let inbox = board("Inbox", BoardKind::Inbox);
+
+    let line = format_task(
+        &task("x due 2024-05-01 +1 for +tests", "", false),
+        Some(&inbox),
+    );
+
+    assert_eq!(line, r"\x due 2024-05-01 \+1 for \+tests");
+}